@@ -0,0 +1,61 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pss::math::galois::{clmul, native, GF};
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+const NUM_ELEMENTS: usize = 4096;
+
+fn bench_mul_width<const W: u8>(c: &mut Criterion) {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    let lhs: Vec<GF<W>> = (0..NUM_ELEMENTS).map(|_| GF::rand(&mut rng)).collect();
+    let rhs: Vec<GF<W>> = (0..NUM_ELEMENTS).map(|_| GF::rand(&mut rng)).collect();
+    let lhs_raw: Vec<u32> = lhs.iter().map(|&x| x.into()).collect();
+    let rhs_raw: Vec<u32> = rhs.iter().map(|&x| x.into()).collect();
+
+    let mut group = c.benchmark_group("mul");
+    group.throughput(Throughput::Elements(NUM_ELEMENTS as u64));
+
+    group.bench_with_input(BenchmarkId::new("gf", W), &W, |b, _| {
+        b.iter(|| {
+            lhs.iter()
+                .zip(rhs.iter())
+                .map(|(x, y)| x * y)
+                .fold(GF::<W>::ZERO, |acc, x| acc + black_box(x))
+        })
+    });
+
+    group.bench_with_input(BenchmarkId::new("native", W), &W, |b, _| {
+        b.iter(|| {
+            lhs_raw
+                .iter()
+                .zip(rhs_raw.iter())
+                .map(|(&x, &y)| native::mul(x, y, W))
+                .fold(0, |acc, x| acc ^ black_box(x))
+        })
+    });
+
+    if clmul::is_available() {
+        group.bench_with_input(BenchmarkId::new("clmul", W), &W, |b, _| {
+            b.iter(|| {
+                lhs_raw
+                    .iter()
+                    .zip(rhs_raw.iter())
+                    .map(|(&x, &y)| clmul::mul(x, y, W))
+                    .fold(0, |acc, x| acc ^ black_box(x))
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_mul(c: &mut Criterion) {
+    bench_mul_width::<8>(c);
+    bench_mul_width::<12>(c);
+    bench_mul_width::<16>(c);
+    bench_mul_width::<20>(c);
+    bench_mul_width::<24>(c);
+}
+
+criterion_group!(benches, bench_mul);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+//! Compiles the log tables of Jerasure's C library when the `jerasure` feature selects them.
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo::rustc-check-cfg=cfg(jerasure_backend)");
+    println!("cargo::rerun-if-env-changed=JERASURE_DIR");
+
+    let enabled = |feature: &str| env::var_os(format!("CARGO_FEATURE_{}", feature)).is_some();
+    if !enabled("JERASURE") || enabled("CONSTANT_TIME") {
+        return;
+    }
+
+    let dir = PathBuf::from(env::var("JERASURE_DIR").unwrap_or_else(|_| "jerasure".into()));
+    let src = dir.join("galois.c");
+    assert!(
+        src.exists(),
+        "{} not found; set JERASURE_DIR to the directory containing Jerasure's galois.c",
+        src.display()
+    );
+
+    println!("cargo::rerun-if-changed={}", src.display());
+    cc::Build::new().file(&src).include(&dir).compile("galois");
+    println!("cargo::rustc-cfg=jerasure_backend");
+}
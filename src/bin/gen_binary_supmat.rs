@@ -0,0 +1,74 @@
+//! Generates a binary super-invertible matrix for randomness extraction among n parties of which
+//! at most t are corrupt, and writes it in the format read by `binary_super_inv_matrix`.
+use argh::FromArgs;
+use pss::math::galois::GF;
+use pss::math::linalg::MinorCheck;
+use pss::math::prime::Mersenne61;
+use pss::math::{gen_binary_super_inv_matrix, write_binary_super_inv_matrix};
+use std::path::PathBuf;
+
+#[derive(FromArgs)]
+/// Generate a binary super-invertible matrix.
+struct Args {
+    /// number of parties i.e., inputs to the extraction
+    #[argh(option)]
+    n: usize,
+
+    /// maximum number of corrupt parties
+    #[argh(option)]
+    t: usize,
+
+    /// number of outputs of the extraction
+    #[argh(option)]
+    outputs: usize,
+
+    /// seed for sampling the matrix
+    #[argh(option, default = "0")]
+    seed: u64,
+
+    /// number of random subsets to check instead of checking all of them
+    #[argh(option)]
+    trials: Option<usize>,
+
+    /// check invertibility over the prime field instead of binary fields
+    #[argh(switch)]
+    prime: bool,
+
+    /// output file
+    #[argh(positional)]
+    path: PathBuf,
+}
+
+fn main() {
+    let args: Args = argh::from_env();
+
+    let mode = match args.trials {
+        Some(trials) => MinorCheck::Randomized {
+            trials,
+            seed: args.seed,
+        },
+        None => MinorCheck::Exact,
+    };
+    let num_honest = args.n.checked_sub(args.t).expect("t should be at most n");
+
+    let res = if args.prime {
+        gen_binary_super_inv_matrix::<Mersenne61>(args.n, args.outputs, num_honest, args.seed, mode)
+            .map(|m| write_binary_super_inv_matrix(m.view(), &args.path))
+    } else {
+        // Minors of binary matrices lie in GF(2), so any binary field gives the same result.
+        gen_binary_super_inv_matrix::<GF<8>>(args.n, args.outputs, num_honest, args.seed, mode)
+            .map(|m| write_binary_super_inv_matrix(m.view(), &args.path))
+    };
+
+    match res {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => {
+            eprintln!("Failed to write the matrix: {}", err);
+            std::process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,6 +1,27 @@
-use std::time::Instant;
-
 pub mod circuit;
 pub mod math;
 pub mod protocol;
 pub mod sharing;
+
+/// Identifier of a party, from 0 to n - 1.
+pub type PartyID = u32;
+
+/// Reasons for a protocol to abort.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtoErrorKind {
+    /// A corrupt party was caught deviating from the protocol e.g., by sending inconsistent
+    /// shares.
+    MaliciousBehavior,
+    Other(&'static str),
+}
+
+impl std::fmt::Display for ProtoErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MaliciousBehavior => write!(f, "malicious behavior detected"),
+            Self::Other(msg) => write!(f, "protocol error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ProtoErrorKind {}
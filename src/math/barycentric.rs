@@ -0,0 +1,171 @@
+//! Lagrange interpolation using barycentric weights.
+//!
+//! For points x_0, ..., x_{m-1}, the weights are w_i = 1 / \prod_{j \neq i} (x_i - x_j) and the
+//! lagrange coefficients at a point v are L(v) * w_i / (v - x_i) where L(v) = \prod_j (v - x_j).
+//! The weights are computed once per set of points in O(m^2) operations and can be updated in
+//! O(m) operations when a point is added or removed, after which evaluating at a new point only
+//! takes O(m) operations.
+use super::Field;
+use ndarray::{Array2, Axis};
+use rayon::prelude::*;
+
+/// Barycentric weights of a set of interpolation points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Barycentric<F: Field> {
+    points: Vec<F>,
+    weights: Vec<F>,
+}
+
+impl<F: Field> Barycentric<F> {
+    /// Pre-compute weights for interpolating a polynomial defined by evaluations at `points`.
+    ///
+    /// The points should be distinct and their differences should be invertible.
+    pub fn new(points: &[F]) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            let num_unique = points
+                .iter()
+                .collect::<std::collections::HashSet<_>>()
+                .len();
+            assert_eq!(num_unique, points.len());
+        }
+
+        let mut denom = Vec::with_capacity(points.len());
+        points
+            .par_iter()
+            .map(|v| {
+                points
+                    .iter()
+                    .filter(|&x| x != v)
+                    .fold(F::ONE, |acc, x| acc * (*v - *x))
+            })
+            .collect_into_vec(&mut denom);
+
+        Self {
+            points: points.to_vec(),
+            weights: batch_inv(&denom),
+        }
+    }
+
+    /// Interpolation points, in the order used for coefficients.
+    pub fn points(&self) -> &[F] {
+        &self.points
+    }
+
+    /// Barycentric weights of the points.
+    pub fn weights(&self) -> &[F] {
+        &self.weights
+    }
+
+    /// Number of interpolation points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if there are no interpolation points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Add an interpolation point at the end.
+    pub fn add_point(&mut self, x: F) {
+        debug_assert!(!self.points.contains(&x));
+
+        let diffs: Vec<_> = self.points.iter().map(|&p| p - x).collect();
+        let inv_diffs = batch_inv(&diffs);
+
+        // w_i gains the factor 1 / (x_i - x) and the new weight is 1 / \prod_j (x - x_j).
+        let denom = diffs.iter().fold(F::ONE, |acc, &d| acc * (F::ZERO - d));
+        for (w, &d) in self.weights.iter_mut().zip(inv_diffs.iter()) {
+            *w *= d;
+        }
+
+        self.points.push(x);
+        self.weights
+            .push(denom.inv().expect("Differences should be invertible."));
+    }
+
+    /// Remove an interpolation point, keeping the order of the remaining ones.
+    ///
+    /// Returns false if `x` is not an interpolation point.
+    pub fn remove_point(&mut self, x: F) -> bool {
+        let Some(i) = self.points.iter().position(|&p| p == x) else {
+            return false;
+        };
+
+        self.points.remove(i);
+        self.weights.remove(i);
+        for (w, &p) in self.weights.iter_mut().zip(self.points.iter()) {
+            *w *= p - x;
+        }
+
+        true
+    }
+
+    /// Lagrange coefficients for evaluating at `v` i.e., the value at `v` of the polynomial
+    /// through the points is the inner product of the coefficients with the values.
+    pub fn coeffs(&self, v: F) -> Vec<F> {
+        if let Some(i) = self.points.iter().position(|&x| x == v) {
+            // The coefficients simplify to selection.
+            let mut row = vec![F::ZERO; self.len()];
+            row[i] = F::ONE;
+            return row;
+        }
+
+        let diffs: Vec<_> = self.points.iter().map(|&x| v - x).collect();
+        let numerator = diffs.iter().fold(F::ONE, |acc, &d| acc * d);
+
+        batch_inv(&diffs)
+            .into_iter()
+            .zip(self.weights.iter())
+            .map(|(d, &w)| numerator * w * d)
+            .collect()
+    }
+
+    /// Evaluate at `v` the polynomial that takes `values[i]` at the i-th point.
+    pub fn eval(&self, values: &[F], v: F) -> F {
+        debug_assert_eq!(values.len(), self.len());
+        F::dot(&self.coeffs(v), values)
+    }
+
+    /// Matrix of lagrange coefficients with one row per point in `npos`, as in `lagrange_coeffs`.
+    pub fn coeff_matrix(&self, npos: &[F]) -> Array2<F> {
+        let mut res = Array2::from_elem((npos.len(), self.len()), F::ZERO);
+        res.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .zip(npos.par_iter())
+            .for_each(|(mut row, &v)| {
+                for (r, c) in row.iter_mut().zip(self.coeffs(v)) {
+                    *r = c;
+                }
+            });
+
+        res
+    }
+}
+
+/// Inverts every element using a single inversion.
+///
+/// # Panics
+/// Panics if any of the elements is not invertible.
+fn batch_inv<F: Field>(vals: &[F]) -> Vec<F> {
+    // prefix[i] is the product of the first i elements.
+    let mut prefix = Vec::with_capacity(vals.len() + 1);
+    prefix.push(F::ONE);
+    for &v in vals {
+        prefix.push(*prefix.last().unwrap() * v);
+    }
+
+    let mut inv = prefix
+        .last()
+        .unwrap()
+        .inv()
+        .expect("Differences should be invertible.");
+    let mut res = vec![F::ZERO; vals.len()];
+    for i in (0..vals.len()).rev() {
+        res[i] = inv * prefix[i];
+        inv *= vals[i];
+    }
+
+    res
+}
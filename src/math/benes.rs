@@ -0,0 +1,236 @@
+//! Beneš networks for routing permutations through layers of 2x2 switches.
+//!
+//! A network of size 2^k consists of a layer of switches on pairs of adjacent inputs, two
+//! networks of size 2^(k-1) on the top and bottom outputs of those switches, and a layer of
+//! switches on pairs of adjacent outputs. Any permutation can be routed by setting the switches
+//! using the looping algorithm, which takes O(n log n) time.
+use serde::{Deserialize, Serialize};
+
+/// Switch settings of a Beneš network routing a permutation.
+///
+/// Permutations whose size is not a power of two are padded with fixed points.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawBenesNetwork")]
+pub struct BenesNetwork {
+    // Length of the routed permutation, before padding.
+    len: usize,
+    size: usize,
+    // Whether each input and output switch is crossed.
+    input: Vec<bool>,
+    output: Vec<bool>,
+    // Top and bottom subnetworks, absent when the size is at most 2.
+    sub: Option<Box<(BenesNetwork, BenesNetwork)>>,
+}
+
+impl BenesNetwork {
+    /// Route the permutation `perm` such that applying the network to `inp` results in `out`
+    /// with out[i] = inp[perm[i]].
+    ///
+    /// `perm` should be a permutation of 0, ..., perm.len() - 1.
+    pub fn new(perm: &[usize]) -> Self {
+        let size = perm.len().next_power_of_two();
+        let mut padded = perm.to_vec();
+        padded.extend(perm.len()..size);
+        Self {
+            len: perm.len(),
+            ..Self::route(&padded)
+        }
+    }
+
+    fn route(perm: &[usize]) -> Self {
+        let size = perm.len();
+        if size <= 2 {
+            return Self {
+                len: size,
+                size,
+                input: Vec::new(),
+                output: vec![size == 2 && perm[0] == 1],
+                sub: None,
+            };
+        }
+
+        let mut inv = vec![0; size];
+        for (i, &p) in perm.iter().enumerate() {
+            inv[p] = i;
+        }
+
+        // Colour every input by the subnetwork it is routed through, with true for the top one.
+        // Inputs sharing a switch use different subnetworks and so do the inputs routed to
+        // outputs sharing a switch, which is satisfied by following these constraints in loops.
+        let mut top: Vec<Option<bool>> = vec![None; size];
+        for start in (0..size).step_by(2) {
+            if top[start].is_some() {
+                continue;
+            }
+
+            let mut cur = start;
+            while top[cur].is_none() {
+                top[cur] = Some(true);
+                top[cur ^ 1] = Some(false);
+
+                // The input routed to the output paired with that of cur ^ 1 must use the top.
+                cur = perm[inv[cur ^ 1] ^ 1];
+            }
+        }
+        let top: Vec<bool> = top.into_iter().map(Option::unwrap).collect();
+
+        // After the input layer, the even position of every switch is routed through the top.
+        let input: Vec<_> = (0..(size / 2)).map(|a| !top[2 * a]).collect();
+
+        // The output layer is crossed whenever the even output is routed through the bottom.
+        let output: Vec<_> = (0..(size / 2)).map(|b| !top[perm[2 * b]]).collect();
+
+        let (mut top_perm, mut bottom_perm) = (Vec::with_capacity(size / 2), Vec::new());
+        for b in 0..(size / 2) {
+            let (t, u) = if output[b] {
+                (perm[2 * b + 1], perm[2 * b])
+            } else {
+                (perm[2 * b], perm[2 * b + 1])
+            };
+            top_perm.push(t / 2);
+            bottom_perm.push(u / 2);
+        }
+
+        Self {
+            size,
+            input,
+            len: size,
+            output,
+            sub: Some(Box::new((
+                Self::route(&top_perm),
+                Self::route(&bottom_perm),
+            ))),
+        }
+    }
+
+    /// Length of the routed permutation.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the routed permutation is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of inputs including padding, which is a power of two.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of layers of switches.
+    pub fn depth(&self) -> usize {
+        match &self.sub {
+            None => usize::from(self.size == 2),
+            Some(sub) => sub.0.depth() + 2,
+        }
+    }
+
+    /// Apply the network on `inp`, whose length should equal the length of the routed
+    /// permutation.
+    pub fn apply<T: Copy>(&self, inp: &[T]) -> Vec<T> {
+        assert_eq!(inp.len(), self.len, "Dimension mismatch.");
+
+        // Route the indices through the padded network, which maps the first `len` positions
+        // among themselves.
+        let mut idx: Vec<usize> = (0..self.size).collect();
+        self.apply_in_place(&mut idx);
+        idx.truncate(self.len);
+        idx.into_iter().map(|i| inp[i]).collect()
+    }
+
+    fn apply_in_place<T: Copy>(&self, v: &mut [T]) {
+        let Some(sub) = &self.sub else {
+            if self.output.first() == Some(&true) {
+                v.swap(0, 1);
+            }
+            return;
+        };
+
+        let half = self.size / 2;
+        let (mut upper, mut lower) = (Vec::with_capacity(half), Vec::with_capacity(half));
+        for (a, &cross) in self.input.iter().enumerate() {
+            let (x, y) = (v[2 * a], v[2 * a + 1]);
+            let (x, y) = if cross { (y, x) } else { (x, y) };
+            upper.push(x);
+            lower.push(y);
+        }
+
+        sub.0.apply_in_place(&mut upper);
+        sub.1.apply_in_place(&mut lower);
+
+        for (b, &cross) in self.output.iter().enumerate() {
+            let (x, y) = if cross {
+                (lower[b], upper[b])
+            } else {
+                (upper[b], lower[b])
+            };
+            v[2 * b] = x;
+            v[2 * b + 1] = y;
+        }
+    }
+}
+
+/// Deserialized fields of a [`BenesNetwork`], which are checked before use since applying the
+/// network relies on its structure.
+#[derive(Deserialize)]
+struct RawBenesNetwork {
+    len: usize,
+    size: usize,
+    input: Vec<bool>,
+    output: Vec<bool>,
+    sub: Option<Box<(BenesNetwork, BenesNetwork)>>,
+}
+
+impl TryFrom<RawBenesNetwork> for BenesNetwork {
+    type Error = &'static str;
+
+    fn try_from(raw: RawBenesNetwork) -> Result<Self, Self::Error> {
+        let RawBenesNetwork {
+            len,
+            size,
+            input,
+            output,
+            sub,
+        } = raw;
+        if len.max(1).checked_next_power_of_two() != Some(size) {
+            return Err("Size should be the length rounded up to a power of two.");
+        }
+
+        let half = size / 2;
+        let valid = match &sub {
+            // A single input can't be crossed with anything.
+            None => size <= 2 && input.is_empty() && output.len() == 1 && (size == 2 || !output[0]),
+            Some(sub) => {
+                size > 2
+                    && input.len() == half
+                    && output.len() == half
+                    && [&sub.0, &sub.1]
+                        .iter()
+                        .all(|s| s.size == half && s.len == half)
+            }
+        };
+        if !valid {
+            return Err("Switch layers don't match the size of the network.");
+        }
+
+        let net = Self {
+            len,
+            size,
+            input,
+            output,
+            sub,
+        };
+
+        // Subnetworks are checked when they are deserialized, but the network must also map
+        // the first `len` positions among themselves for `apply` to ignore the padding.
+        if len < size {
+            let mut idx: Vec<usize> = (0..size).collect();
+            net.apply_in_place(&mut idx);
+            if idx[..len].iter().any(|&i| i >= len) {
+                return Err("Network routes padding into the permutation.");
+            }
+        }
+        Ok(net)
+    }
+}
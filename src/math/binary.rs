@@ -0,0 +1,139 @@
+//! Bit-packed matrices with entries in {0, 1}.
+//!
+//! Multiplying a binary matrix with field elements only needs additions, which are XORs in binary
+//! fields, so applying e.g. a binary super-invertible matrix this way avoids the field
+//! multiplications of a dense product.
+use super::Field;
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Axis};
+use rayon::prelude::*;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Binary matrix with every row packed into 64-bit words.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BinaryMatrix {
+    num_rows: usize,
+    num_cols: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl BinaryMatrix {
+    /// All zero matrix.
+    pub fn zeros(num_rows: usize, num_cols: usize) -> Self {
+        let words_per_row = num_cols.div_ceil(WORD_BITS);
+        Self {
+            num_rows,
+            num_cols,
+            words_per_row,
+            bits: vec![0; num_rows * words_per_row],
+        }
+    }
+
+    /// Pack a dense matrix whose entries are all zero or one.
+    pub fn from_dense<F: Field>(mat: ArrayView2<F>) -> Result<Self, &'static str> {
+        let (num_rows, num_cols) = mat.dim();
+        let mut res = Self::zeros(num_rows, num_cols);
+
+        for ((r, c), &v) in mat.indexed_iter() {
+            if v == F::ONE {
+                res.set(r, c, true);
+            } else if v != F::ZERO {
+                return Err("Matrix should only have binary entries.");
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Dense matrix with entries ZERO and ONE.
+    pub fn to_dense<F: Field>(&self) -> Array2<F> {
+        Array2::from_shape_fn(
+            self.dim(),
+            |(r, c)| {
+                if self.get(r, c) {
+                    F::ONE
+                } else {
+                    F::ZERO
+                }
+            },
+        )
+    }
+
+    /// Number of rows and columns.
+    pub fn dim(&self) -> (usize, usize) {
+        (self.num_rows, self.num_cols)
+    }
+
+    /// Entry at the given row and column.
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        debug_assert!(row < self.num_rows && col < self.num_cols);
+        let word = self.bits[row * self.words_per_row + col / WORD_BITS];
+        (word >> (col % WORD_BITS)) & 1 == 1
+    }
+
+    /// Set the entry at the given row and column.
+    pub fn set(&mut self, row: usize, col: usize, val: bool) {
+        debug_assert!(row < self.num_rows && col < self.num_cols);
+        let word = &mut self.bits[row * self.words_per_row + col / WORD_BITS];
+        let mask = 1 << (col % WORD_BITS);
+        if val {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    /// Transposed matrix.
+    pub fn transpose(&self) -> Self {
+        let mut res = Self::zeros(self.num_cols, self.num_rows);
+        for r in 0..self.num_rows {
+            for c in self.row_ones(r) {
+                res.set(c, r, true);
+            }
+        }
+        res
+    }
+
+    /// Columns of the ones in a row, in increasing order.
+    fn row_ones(&self, row: usize) -> impl Iterator<Item = usize> + '_ {
+        let words = &self.bits[(row * self.words_per_row)..((row + 1) * self.words_per_row)];
+        words.iter().enumerate().flat_map(|(i, &word)| {
+            std::iter::successors((word != 0).then_some(word), |&w| {
+                let rest = w & (w - 1);
+                (rest != 0).then_some(rest)
+            })
+            .map(move |w| i * WORD_BITS + w.trailing_zeros() as usize)
+        })
+    }
+
+    /// Matrix-vector product, computed by adding up the entries of `v` selected by each row.
+    pub fn mul_vec<F: Field>(&self, v: ArrayView1<F>) -> Array1<F> {
+        assert_eq!(v.len(), self.num_cols, "Dimension mismatch.");
+
+        let mut res = Vec::with_capacity(self.num_rows);
+        (0..self.num_rows)
+            .into_par_iter()
+            .map(|r| self.row_ones(r).fold(F::ZERO, |acc, c| acc + v[c]))
+            .collect_into_vec(&mut res);
+
+        Array1::from_vec(res)
+    }
+
+    /// Matrix product, computed by adding up the rows of `m` selected by each row.
+    pub fn mul_mat<F: Field>(&self, m: ArrayView2<F>) -> Array2<F> {
+        assert_eq!(m.nrows(), self.num_cols, "Dimension mismatch.");
+
+        let mut res = Array2::from_elem((self.num_rows, m.ncols()), F::ZERO);
+        res.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .enumerate()
+            .for_each(|(r, mut out)| {
+                for c in self.row_ones(r) {
+                    out.zip_mut_with(&m.row(c), |o, &v| *o += v);
+                }
+            });
+
+        res
+    }
+}
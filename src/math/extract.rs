@@ -0,0 +1,53 @@
+//! Randomness extraction from the contributions of all parties.
+//!
+//! Each of the n parties deals a random packed sharing and every party locally applies the same
+//! matrix to its shares of the n sharings. With a super-invertible matrix of n - t rows, the
+//! outputs are uniformly random as long as n - t contributions are, regardless of those from
+//! corrupt parties. With a hyper-invertible matrix, some outputs can additionally be opened to
+//! check that all contributions were valid sharings, as done by Beerliová-Trubíniová and Hirt.
+use super::Field;
+use crate::sharing::PackedSharing;
+use crate::ProtoErrorKind;
+use ndarray::{s, Array2, ArrayView2};
+
+/// Applies the extraction `matrix` to `inputs`, which has one row per party's contribution and
+/// one column per element of a batch, e.g., each of the shares of the contributed sharings.
+///
+/// Returns one row per row of the matrix.
+pub fn extract_randomness<F: Field>(inputs: ArrayView2<F>, matrix: ArrayView2<F>) -> Array2<F> {
+    assert_eq!(matrix.ncols(), inputs.nrows(), "Dimension mismatch.");
+    F::mat_mul(matrix, inputs)
+}
+
+/// Applies a hyper-invertible extraction `matrix` and splits the output into the extracted
+/// randomness and the last `num_checks` rows, which should be opened and verified using
+/// [`verify_checks`] before the extracted randomness is used.
+///
+/// For n parties of which t are corrupt, opening 2t outputs of an n x n hyper-invertible matrix
+/// ensures that the remaining n - 2t outputs are valid sharings.
+pub fn extract_randomness_checked<F: Field>(
+    inputs: ArrayView2<F>,
+    matrix: ArrayView2<F>,
+    num_checks: usize,
+) -> (Array2<F>, Array2<F>) {
+    assert!(num_checks <= matrix.nrows());
+
+    let out = extract_randomness(inputs, matrix);
+    let num_out = out.nrows() - num_checks;
+    (
+        out.slice(s![..num_out, ..]).to_owned(),
+        out.slice(s![num_out.., ..]).to_owned(),
+    )
+}
+
+/// Verifies opened check outputs of [`extract_randomness_checked`], given all the shares of
+/// each check output as a row.
+pub fn verify_checks<F: Field>(
+    pss: &PackedSharing<F>,
+    opened: ArrayView2<F>,
+) -> Result<(), ProtoErrorKind> {
+    for shares in opened.rows() {
+        pss.recon(shares)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! The [`Field`] trait shared by all coefficient types.
+//!
+//! Implementors are `GF<W>`, `GF64` and `GF128` in [`galois`](super::galois), `Mersenne61` in
+//! [`prime`](super::prime) and the Galois rings `GR<K, D>` in [`ring`](super::ring).
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use num_traits::identities::{One, Zero};
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// Elements of a finite field.
+///
+/// Sharing and interpolation are generic over this trait so that the same packing machinery
+/// works over binary extension fields and prime fields.
+///
+/// Galois rings also implement this trait, in which case division is only defined for units.
+/// Interpolation then works as long as the positions are chosen from an exceptional set, i.e.,
+/// the differences between all of them are units.
+pub trait Field:
+    Copy
+    + Debug
+    + Eq
+    + Hash
+    + Send
+    + Sync
+    + 'static
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + MulAssign
+    + Div<Output = Self>
+    + DivAssign
+    + Zero
+    + One
+    + From<u32>
+    + Serialize
+    + DeserializeOwned
+{
+    /// Additive identity.
+    const ZERO: Self;
+
+    /// Multiplicative identity.
+    const ONE: Self;
+
+    /// Number of bytes when serialized.
+    const NUM_BYTES: usize;
+
+    /// Number of bits in the representation of an element.
+    const WIDTH: usize;
+
+    /// Characteristic i.e., the additive order of `ONE`.
+    const CHARACTERISTIC: u128;
+
+    /// Degree over the integers modulo `CHARACTERISTIC`. Along with the characteristic, this
+    /// identifies the field (or ring) when storing elements.
+    const DEGREE: usize;
+
+    /// Sample an element uniformly at random from the field.
+    fn rand<R: Rng>(rng: &mut R) -> Self;
+
+    /// Multiplicative inverse, or `None` for zero (or non-units in a ring).
+    fn inv(&self) -> Option<Self>;
+
+    /// Inner product of `a` and `b`.
+    fn dot(a: &[Self], b: &[Self]) -> Self {
+        debug_assert_eq!(a.len(), b.len());
+        a.iter()
+            .zip(b.iter())
+            .fold(Self::ZERO, |acc, (&x, &y)| acc + x * y)
+    }
+
+    /// Matrix-vector product.
+    fn mat_vec(m: ArrayView2<Self>, v: ArrayView1<Self>) -> Array1<Self> {
+        m.dot(&v)
+    }
+
+    /// Matrix-matrix product.
+    fn mat_mul(a: ArrayView2<Self>, b: ArrayView2<Self>) -> Array2<Self> {
+        a.dot(&b)
+    }
+}
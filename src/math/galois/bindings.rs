@@ -0,0 +1,8 @@
+//! Declarations of the log table functions of Jerasure's galois.c, which is compiled by build.rs.
+use std::os::raw::c_int;
+
+extern "C" {
+    pub fn galois_create_log_tables(w: c_int) -> c_int;
+    pub fn galois_logtable_multiply(x: c_int, y: c_int, w: c_int) -> c_int;
+    pub fn galois_logtable_divide(x: c_int, y: c_int, w: c_int) -> c_int;
+}
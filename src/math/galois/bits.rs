@@ -0,0 +1,126 @@
+//! Elements of GF(2^W) as vectors in GF(2)^W.
+//!
+//! An element is identified with its coordinates in the polynomial basis 1, x, ..., x^(W-1) i.e.,
+//! bit i of its representation is the coefficient of x^i. Multiplication by a constant and the
+//! Frobenius automorphism are GF(2)-linear, so they are given by W x W binary matrices acting on
+//! these coordinates.
+use super::{native, GF};
+use crate::math::binary::BinaryMatrix;
+
+impl<const W: u8> GF<W> {
+    /// Element with the given representation, or an error if it is not less than `ORDER`.
+    ///
+    /// Unlike `From<u32>`, this doesn't reduce such values modulo the primitive polynomial.
+    /// `TryFrom<u32>` can't be implemented instead since it would conflict with the blanket
+    /// implementation for types implementing `From<u32>`.
+    pub fn try_from_u32(value: u32) -> Result<Self, &'static str> {
+        if value < Self::ORDER {
+            Ok(Self::new(value))
+        } else {
+            Err("Value is too large for the field.")
+        }
+    }
+
+    /// Coefficient of x^i.
+    pub fn bit(&self, i: usize) -> bool {
+        debug_assert!(i < W as usize);
+        (self.0 >> i) & 1 == 1
+    }
+
+    /// Coefficients of 1, x, ..., x^(W-1). `N` must equal W.
+    pub fn to_bits<const N: usize>(&self) -> [bool; N] {
+        const { assert!(N == W as usize, "Number of bits should equal the width.") };
+        std::array::from_fn(|i| self.bit(i))
+    }
+
+    /// Element with the given coefficients of 1, x, ..., x^(W-1). `N` must equal W.
+    pub fn from_bits<const N: usize>(bits: [bool; N]) -> Self {
+        const { assert!(N == W as usize, "Number of bits should equal the width.") };
+        Self::from_bit_slice(&bits).unwrap()
+    }
+
+    /// Element with the given coefficients of 1, x, ..., x^(W-1), or an error if there aren't
+    /// exactly W of them.
+    pub fn from_bit_slice(bits: &[bool]) -> Result<Self, &'static str> {
+        if bits.len() != W as usize {
+            return Err("Number of bits should equal the width.");
+        }
+
+        Ok(Self::new(
+            bits.iter()
+                .enumerate()
+                .fold(0, |acc, (i, &b)| acc | (u32::from(b) << i)),
+        ))
+    }
+
+    /// Little endian representation using `NUM_BYTES` bytes, as when serialized.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_le_bytes()[..Self::NUM_BYTES].to_vec()
+    }
+
+    /// Element with the given little endian representation, or an error if there aren't exactly
+    /// `NUM_BYTES` bytes or the value is not less than `ORDER`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != Self::NUM_BYTES {
+            return Err("Number of bytes should equal NUM_BYTES.");
+        }
+
+        let mut vals = [0u8; 4];
+        vals[..bytes.len()].copy_from_slice(bytes);
+        Self::try_from_u32(u32::from_le_bytes(vals))
+    }
+
+    /// Matrix M such that the bits of c * x are M times the bits of x, where c is `self`.
+    ///
+    /// Column j holds the bits of c * x^j.
+    pub fn mul_matrix(&self) -> BinaryMatrix {
+        let mut res = BinaryMatrix::zeros(W.into(), W.into());
+        let mut col = self.0;
+        for j in 0..(W as usize) {
+            set_column(&mut res, j, col);
+            col = times_x(col, W);
+        }
+        res
+    }
+
+    /// Matrix M such that the bits of x^2 are M times the bits of x.
+    ///
+    /// Column j holds the bits of x^(2j).
+    pub fn frobenius_matrix() -> BinaryMatrix {
+        let mut res = BinaryMatrix::zeros(W.into(), W.into());
+        let mut col = 1;
+        for j in 0..(W as usize) {
+            set_column(&mut res, j, col);
+            col = times_x(times_x(col, W), W);
+        }
+        res
+    }
+}
+
+fn set_column(mat: &mut BinaryMatrix, col: usize, val: u32) {
+    for i in 0..mat.dim().0 {
+        mat.set(i, col, (val >> i) & 1 == 1);
+    }
+}
+
+// Multiplication by x only needs a shift and a reduction.
+fn times_x(val: u32, w: u8) -> u32 {
+    native::reduce(val << 1, w)
+}
+
+/// Concatenation of the bits of all elements, with W bits per element.
+pub fn to_bit_vec<const W: u8>(vals: &[GF<W>]) -> Vec<bool> {
+    vals.iter()
+        .flat_map(|v| (0..(W as usize)).map(move |i| v.bit(i)))
+        .collect()
+}
+
+/// Elements given by consecutive chunks of W bits, or an error if the number of bits is not a
+/// multiple of W.
+pub fn from_bit_vec<const W: u8>(bits: &[bool]) -> Result<Vec<GF<W>>, &'static str> {
+    if !bits.len().is_multiple_of(W.into()) {
+        return Err("Number of bits should be a multiple of the width.");
+    }
+
+    bits.chunks(W.into()).map(GF::from_bit_slice).collect()
+}
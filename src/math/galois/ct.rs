@@ -0,0 +1,66 @@
+//! Constant-time implementation of the field operations.
+//!
+//! Neither the memory accesses nor the branches depend on the operands: products are computed by
+//! shift-and-add over all W bits using masks instead of conditionals, and inverses by raising to
+//! the power 2^W - 2 using a fixed sequence of squarings and multiplications. This is slower than
+//! the log tables but doesn't leak the operands through cache timing. Elements are represented
+//! as in [`native`](super::native), so results are identical.
+use super::native::{MAX_WIDTH, PRIM_POLY};
+use std::hint::black_box;
+
+/// Check that the width is supported. No tables are needed.
+pub fn init(w: u8) -> Result<(), &'static str> {
+    if w == 0 || w > MAX_WIDTH {
+        return Err("Could not initialize the field.");
+    }
+
+    Ok(())
+}
+
+/// All ones if the lowest bit of `bit` is set and zero otherwise.
+#[inline(always)]
+fn mask(bit: u32) -> u32 {
+    // Hide the value from the optimizer so that the mask isn't turned back into a branch.
+    black_box(bit & 1).wrapping_neg()
+}
+
+/// Multiply two field elements of width `w`.
+pub fn mul(mut x: u32, y: u32, w: u8) -> u32 {
+    let poly = PRIM_POLY[w as usize];
+    let mut res = 0;
+
+    for i in 0..w {
+        res ^= x & mask(y >> i);
+
+        x <<= 1;
+        x ^= poly & mask(x >> w);
+    }
+
+    res
+}
+
+/// Inverse of a field element of width `w`, where zero is mapped to zero.
+pub fn inv(x: u32, w: u8) -> u32 {
+    if w == 1 {
+        return x;
+    }
+
+    // x^(2^W - 2) = x^2 * x^4 * ... * x^(2^(W-1)).
+    let mut sq = x;
+    let mut res = 1;
+    for _ in 1..w {
+        sq = mul(sq, sq, w);
+        res = mul(res, sq, w);
+    }
+
+    res
+}
+
+/// Divide two field elements of width `w`.
+///
+/// Like the C library, dividing by zero returns `u32::MAX` (i.e., -1).
+pub fn div(x: u32, y: u32, w: u8) -> u32 {
+    // y | -y has the top bit set if and only if y is non-zero, since y < 2^31.
+    let is_zero = mask(((y | y.wrapping_neg()) >> 31) ^ 1);
+    mul(x, inv(y, w), w) | is_zero
+}
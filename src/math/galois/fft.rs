@@ -0,0 +1,96 @@
+//! Additive FFT over GF(2^W) using the novel polynomial basis of Lin, Chung and Han.
+//!
+//! The evaluation points are the elements whose integer representation is less than 2^k, which
+//! form a subspace V_k with basis 1, x, ..., x^(k-1), or a coset `shift + V_k` of it. Writing W_i
+//! for the vanishing polynomial of V_i and Ŵ_i = W_i / W_i(x^i), polynomials are represented in
+//! the basis X_j = \prod_{i : bit i of j is set} Ŵ_i, in which X_j has degree j. Since each Ŵ_i is
+//! GF(2)-linear, evaluating and interpolating on a coset of V_k takes O(k 2^k) operations.
+use super::{axpy, GF};
+
+/// Pre-computed data for additive FFTs of size up to 2^k.
+pub struct AdditiveFft<const W: u8> {
+    // w_hat[i][t] is Ŵ_i evaluated at x^t.
+    w_hat: Vec<Vec<GF<W>>>,
+}
+
+impl<const W: u8> AdditiveFft<W> {
+    /// Pre-compute data for FFTs of size up to 2^k, where k is at most W.
+    pub fn new(k: usize) -> Self {
+        debug_assert!(k <= W.into());
+
+        // vals[t] holds W_i evaluated at x^t, starting with W_0(x) = x and using
+        // W_{i+1}(y) = W_i(y) * (W_i(y) + W_i(x^i)).
+        let mut vals: Vec<_> = (0..W).map(|t| GF::<W>::from(1u32 << t)).collect();
+        let mut w_hat = Vec::with_capacity(k);
+        for i in 0..k {
+            let norm = vals[i];
+            w_hat.push(vals.iter().map(|&v| v / norm).collect());
+
+            for v in vals.iter_mut() {
+                *v *= *v + norm;
+            }
+        }
+
+        Self { w_hat }
+    }
+
+    /// Largest supported size.
+    pub fn max_size(&self) -> usize {
+        1 << self.w_hat.len()
+    }
+
+    // Ŵ_i evaluated at `x` using linearity.
+    fn eval_w_hat(&self, i: usize, x: GF<W>) -> GF<W> {
+        let x = u32::from(x);
+        self.w_hat[i]
+            .iter()
+            .enumerate()
+            .filter(|(t, _)| (x >> t) & 1 == 1)
+            .fold(GF::ZERO, |acc, (_, &v)| acc + v)
+    }
+
+    /// Replaces coefficients in the novel basis by evaluations at `shift + GF::from(u)` for each
+    /// index u.
+    ///
+    /// The length of `data` should be a power of two no larger than `max_size`.
+    pub fn fft(&self, data: &mut [GF<W>], shift: GF<W>) {
+        let len = data.len();
+        debug_assert!(len.is_power_of_two() && len <= self.max_size());
+
+        for i in (0..len.trailing_zeros() as usize).rev() {
+            let half = 1 << i;
+            for (b, block) in data.chunks_mut(2 * half).enumerate() {
+                let base = GF::from((b * 2 * half) as u32);
+                let s = self.eval_w_hat(i, shift + base);
+
+                let (lo, hi) = block.split_at_mut(half);
+                axpy(s, hi, lo);
+                for (y, x) in hi.iter_mut().zip(lo.iter()) {
+                    *y += *x;
+                }
+            }
+        }
+    }
+
+    /// Inverse of [`AdditiveFft::fft`] i.e., interpolates evaluations at `shift + GF::from(u)`
+    /// for each index u into coefficients in the novel basis.
+    pub fn ifft(&self, data: &mut [GF<W>], shift: GF<W>) {
+        let len = data.len();
+        debug_assert!(len.is_power_of_two() && len <= self.max_size());
+
+        for i in 0..(len.trailing_zeros() as usize) {
+            let half = 1 << i;
+            for (b, block) in data.chunks_mut(2 * half).enumerate() {
+                let base = GF::from((b * 2 * half) as u32);
+                let s = self.eval_w_hat(i, shift + base);
+
+                let (lo, hi) = block.split_at_mut(half);
+                for (y, x) in hi.iter_mut().zip(lo.iter()) {
+                    *y -= *x;
+                }
+                // Subtraction and addition coincide in characteristic 2.
+                axpy(s, hi, lo);
+            }
+        }
+    }
+}
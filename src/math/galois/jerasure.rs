@@ -0,0 +1,59 @@
+//! Field operations using the log tables of the C library.
+//!
+//! The tables of each width are created on first use by [`ensure_tables`], which `GF<W>` calls
+//! whenever it creates an element other than zero and one. Operations don't check for them:
+//! products and quotients involving zero and one are computed without the tables, and any other
+//! operand was created after them.
+use super::bindings;
+use super::native::MAX_WIDTH;
+use std::sync::OnceLock;
+
+/// Returns true if the log tables for width `w` exist, creating them if needed.
+fn tables_ready(w: u8) -> bool {
+    static READY: [OnceLock<bool>; (MAX_WIDTH + 1) as usize] =
+        [const { OnceLock::new() }; (MAX_WIDTH + 1) as usize];
+
+    if w == 0 || w > MAX_WIDTH {
+        return false;
+    }
+
+    *READY[w as usize].get_or_init(|| unsafe { bindings::galois_create_log_tables(w.into()) } == 0)
+}
+
+/// Pre-compute the log tables for width `w`.
+pub fn init(w: u8) -> Result<(), &'static str> {
+    if tables_ready(w) {
+        Ok(())
+    } else {
+        Err("Could not initialize the field.")
+    }
+}
+
+/// Create the log tables for width `w` if needed, panicking if that fails.
+#[inline]
+pub(super) fn ensure_tables(w: u8) {
+    assert!(tables_ready(w), "Could not initialize the field.");
+}
+
+/// Multiply two field elements of width `w`.
+///
+/// Unless one of them is zero or one, the tables must have been created using [`init`].
+pub fn mul(x: u32, y: u32, w: u8) -> u32 {
+    if x <= 1 || y <= 1 {
+        return x * y;
+    }
+
+    unsafe { bindings::galois_logtable_multiply(x as i32, y as i32, w.into()) as u32 }
+}
+
+/// Divide two field elements of width `w`.
+///
+/// Unless the divisor is zero or one, the tables must have been created using [`init`]. Like the
+/// C library, dividing by zero returns `u32::MAX` (i.e., -1).
+pub fn div(x: u32, y: u32, w: u8) -> u32 {
+    match y {
+        0 => u32::MAX,
+        1 => x,
+        _ => unsafe { bindings::galois_logtable_divide(x as i32, y as i32, w.into()) as u32 },
+    }
+}
@@ -0,0 +1,138 @@
+//! Batched operations on slices, vectors and matrices of field elements.
+//!
+//! These amortize the cost of field multiplication over many elements. When carry-less
+//! multiplication is used (for W > 16, see `GF::uses_clmul`), sums of products are accumulated
+//! unreduced and reduced once. Otherwise, multiplying many elements by the same constant looks up
+//! its logarithm only once.
+use super::{clmul, native, GF};
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Axis};
+use rayon::prelude::*;
+
+/// Multiplication by a fixed constant.
+struct Scaler<const W: u8> {
+    c: GF<W>,
+    // Logarithm of the constant along with the log and antilog tables.
+    tables: Option<(u32, &'static [u32], &'static [u32])>,
+}
+
+impl<const W: u8> Scaler<W> {
+    fn new(c: GF<W>) -> Self {
+        // Table lookups depend on the operands, so they are avoided in constant-time mode.
+        let tables = if cfg!(feature = "constant-time") || c.0 == 0 || GF::<W>::uses_clmul() {
+            None
+        } else {
+            native::log_tables(W).map(|(log, exp)| (log[c.0 as usize], log, exp))
+        };
+
+        Self { c, tables }
+    }
+
+    #[inline]
+    fn apply(&self, x: GF<W>) -> GF<W> {
+        match self.tables {
+            Some((log_c, log, exp)) if x.0 != 0 => GF(exp[(log_c + log[x.0 as usize]) as usize]),
+            Some(_) => GF::ZERO,
+            None => x * self.c,
+        }
+    }
+}
+
+/// Element-wise product i.e., `out[i] = a[i] * b[i]`.
+pub fn mul_slice<const W: u8>(out: &mut [GF<W>], a: &[GF<W>], b: &[GF<W>]) {
+    debug_assert_eq!(out.len(), a.len());
+    debug_assert_eq!(a.len(), b.len());
+
+    for ((o, x), y) in out.iter_mut().zip(a.iter()).zip(b.iter()) {
+        *o = x * y;
+    }
+}
+
+/// Multiply every element of `x` by `c` in place.
+pub fn scale<const W: u8>(c: GF<W>, x: &mut [GF<W>]) {
+    let scaler = Scaler::new(c);
+    for v in x.iter_mut() {
+        *v = scaler.apply(*v);
+    }
+}
+
+/// Computes `y[i] += a * x[i]`.
+pub fn axpy<const W: u8>(a: GF<W>, x: &[GF<W>], y: &mut [GF<W>]) {
+    debug_assert_eq!(x.len(), y.len());
+    axpy_iter(a, x.iter().cloned(), y.iter_mut());
+}
+
+/// Inner product of `a` and `b`.
+pub fn dot<const W: u8>(a: &[GF<W>], b: &[GF<W>]) -> GF<W> {
+    debug_assert_eq!(a.len(), b.len());
+    dot_iter(a.iter().cloned().zip(b.iter().cloned()))
+}
+
+/// Matrix-vector product.
+///
+/// Rows are processed in parallel.
+pub fn mat_vec<const W: u8>(m: ArrayView2<GF<W>>, v: ArrayView1<GF<W>>) -> Array1<GF<W>> {
+    debug_assert_eq!(m.ncols(), v.len());
+
+    let res: Vec<_> = m
+        .axis_iter(Axis(0))
+        .into_par_iter()
+        .map(|row| dot_iter(row.iter().cloned().zip(v.iter().cloned())))
+        .collect();
+
+    Array1::from_vec(res)
+}
+
+/// Matrix-matrix product.
+///
+/// Rows of the output are computed in parallel.
+pub fn mat_mul<const W: u8>(a: ArrayView2<GF<W>>, b: ArrayView2<GF<W>>) -> Array2<GF<W>> {
+    debug_assert_eq!(a.ncols(), b.nrows());
+
+    let ncols = b.ncols();
+    let rows: Vec<Vec<GF<W>>> = a
+        .axis_iter(Axis(0))
+        .into_par_iter()
+        .map(|row| {
+            if GF::<W>::uses_clmul() {
+                let mut acc = vec![0; ncols];
+                for (&a_ik, b_row) in row.iter().zip(b.axis_iter(Axis(0))) {
+                    unsafe { clmul::axpy_unreduced(&mut acc, a_ik.0, b_row.iter().map(|x| x.0)) };
+                }
+
+                acc.into_iter()
+                    .map(|v| GF(unsafe { clmul::reduce_unchecked(v, W) }))
+                    .collect()
+            } else {
+                let mut res = vec![GF::ZERO; ncols];
+                for (&a_ik, b_row) in row.iter().zip(b.axis_iter(Axis(0))) {
+                    axpy_iter(a_ik, b_row.iter().cloned(), res.iter_mut());
+                }
+                res
+            }
+        })
+        .collect();
+
+    Array2::from_shape_vec((a.nrows(), ncols), rows.into_iter().flatten().collect()).unwrap()
+}
+
+fn axpy_iter<'a, const W: u8, I, J>(a: GF<W>, x: I, y: J)
+where
+    I: Iterator<Item = GF<W>>,
+    J: Iterator<Item = &'a mut GF<W>>,
+{
+    let scaler = Scaler::new(a);
+    for (v, x) in y.zip(x) {
+        *v += scaler.apply(x);
+    }
+}
+
+fn dot_iter<const W: u8, I>(pairs: I) -> GF<W>
+where
+    I: Iterator<Item = (GF<W>, GF<W>)>,
+{
+    if GF::<W>::uses_clmul() {
+        GF(unsafe { clmul::dot_unchecked(pairs.map(|(x, y)| (x.0, y.0)), W) })
+    } else {
+        pairs.fold(GF::ZERO, |acc, (x, y)| acc + x * y)
+    }
+}
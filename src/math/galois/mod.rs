@@ -1,15 +1,48 @@
+#[cfg(jerasure_backend)]
 mod bindings;
+pub mod bits;
+pub mod clmul;
+pub mod ct;
+pub mod fft;
+#[cfg(jerasure_backend)]
+mod jerasure;
+pub mod kernels;
+pub mod native;
+pub mod packed;
+pub mod wide;
+
+#[cfg(feature = "constant-time")]
+use ct as backend;
+#[cfg(jerasure_backend)]
+use jerasure as backend;
+#[cfg(not(any(jerasure_backend, feature = "constant-time")))]
+use native as backend;
+
+pub use kernels::{axpy, dot, mat_mul, mat_vec, mul_slice, scale};
+pub use wide::{GF128, GF64};
+
+use super::poly::Poly;
+use super::Field;
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
 use num_traits::identities::{One, Zero};
-use rand::distributions::{Distribution, Uniform};
 use rand::Rng;
-use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
 use serde::ser::{Serialize, SerializeTuple, Serializer};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
-use std::sync::{Once, OnceLock};
 
 /// Galois field elements where the order of the field is 2^W.
 ///
-/// Note that W is expected to be less than 30.
+/// Note that W is expected to be at most 30; see [`GF64`] and [`GF128`] for larger fields.
+/// Arithmetic uses the pure-Rust implementation in [`native`] by default. The `jerasure` feature
+/// uses the log tables of the C library instead, and the `constant-time` feature takes precedence
+/// over it and uses [`ct`], whose running time doesn't depend on the operands. All of them
+/// produce identical results.
+/// For W > 16, multiplication uses [`clmul`] instead whenever the CPU supports it, since the log
+/// tables are faster for smaller widths. The `constant-time` feature uses it for every width.
+/// Division by zero panics; use [`GF::checked_div`] to handle zero divisors.
+/// Converting a `u32` reduces values that are not less than `ORDER`, and so does
+/// `GF::<W>::try_from(u32)` since it comes from the blanket implementation for `From<u32>` and
+/// never fails; use [`GF::try_from_u32`] to reject such values.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct GF<const W: u8>(u32);
 
@@ -18,7 +51,7 @@ impl<const W: u8> GF<W> {
     pub const ORDER: u32 = 2_u32.pow(W as u32);
 
     /// Number of bytes when serialized.
-    pub const NUM_BYTES: usize = ((W as usize) + 7) / 8;
+    pub const NUM_BYTES: usize = (W as usize).div_ceil(8);
 
     /// Additive identity.
     pub const ZERO: Self = Self(0);
@@ -26,45 +59,166 @@ impl<const W: u8> GF<W> {
     /// Multiplicative identity.
     pub const ONE: Self = Self(1);
 
-    fn get_dist() -> &'static Uniform<u32> {
-        static DIST: OnceLock<Uniform<u32>> = OnceLock::new();
+    /// Initialize the field by pre-computing data required to carry out field operations.
+    ///
+    /// This is optional since the data for each width is computed on first use, but it allows
+    /// paying the cost upfront and checking at runtime whether the width is supported.
+    pub fn init() -> Result<(), &'static str> {
+        backend::init(W)
+    }
 
-        DIST.get_or_init(|| Uniform::new(0, 2_u32.pow(W.into())))
+    /// Fails to compile if arithmetic is used with an unsupported width.
+    #[inline(always)]
+    fn assert_supported() {
+        const {
+            assert!(
+                W >= 1 && W <= native::MAX_WIDTH,
+                "GF<W> only supports widths from 1 to 30"
+            )
+        };
     }
 
-    /// Initialize the field by pre-computing data required to carry out field operations.
-    pub fn init() -> Result<(), &'static str> {
-        static INIT: Once = Once::new();
+    /// Element with the given representation, which should be less than `ORDER`.
+    ///
+    /// Elements other than `ZERO` and `ONE` that don't result from arithmetic on other elements
+    /// are created using this. With the C library, it creates the log tables for the width on
+    /// first use, so that field operations don't need to check for them.
+    #[inline]
+    fn new(value: u32) -> Self {
+        #[cfg(jerasure_backend)]
+        jerasure::ensure_tables(W);
+        Self(value)
+    }
 
-        let mut flag = true;
-        INIT.call_once(|| {
-            let res = unsafe { bindings::galois_create_log_tables(W.into()) };
-            if res != 0 {
-                flag = false;
-            }
-        });
+    /// Sample an element uniformly at random from the field.
+    pub fn rand<R: Rng>(rng: &mut R) -> Self {
+        // A static distribution would be shared by all widths, since statics in generic
+        // functions aren't instantiated per W.
+        Self::new(rng.gen_range(0..Self::ORDER))
+    }
 
-        if flag {
-            Ok(())
+    /// Generator of the multiplicative group.
+    ///
+    /// The field is defined by a primitive polynomial, so its root x is a generator.
+    pub fn generator() -> Self {
+        if W == 1 {
+            Self::ONE
         } else {
-            Err("Could not initialize the field.")
+            Self::new(2)
         }
     }
 
-    /// Sample an element uniformly at random from the field.
-    pub fn rand<R: Rng>(rng: &mut R) -> Self {
-        Self(Self::get_dist().sample(rng))
+    /// Division that returns `None` if `rhs` is zero.
+    pub fn checked_div(&self, rhs: Self) -> Option<Self> {
+        Self::assert_supported();
+
+        // Always divide so that the running time doesn't depend on whether rhs is zero. Division
+        // by zero results in u32::MAX, which is never a valid element.
+        #[cfg(feature = "constant-time")]
+        {
+            let res = backend::div(self.0, rhs.0, W);
+            (res < Self::ORDER).then_some(Self(res))
+        }
+
+        #[cfg(not(feature = "constant-time"))]
+        if rhs.is_zero() {
+            None
+        } else {
+            Some(Self(backend::div(self.0, rhs.0, W)))
+        }
+    }
+
+    /// Multiplicative inverse, or `None` for zero.
+    pub fn checked_inv(&self) -> Option<Self> {
+        Self::ONE.checked_div(*self)
+    }
+
+    /// Raise to the power `exp`, where 0^0 = 1.
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut base = *self;
+        let mut res = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// Square, which is the Frobenius automorphism.
+    pub fn square(&self) -> Self {
+        self * self
+    }
+
+    /// Apply the Frobenius automorphism `k` times i.e., raise to the power 2^k.
+    pub fn frobenius(&self, k: u32) -> Self {
+        (0..(k % W as u32)).fold(*self, |acc, _| acc.square())
+    }
+
+    /// Square root, which is unique since squaring is an automorphism.
+    pub fn sqrt(&self) -> Self {
+        self.frobenius(W as u32 - 1)
+    }
+
+    /// Absolute trace i.e., the sum of all conjugates x^(2^i) for i < W.
+    ///
+    /// The trace lies in GF(2) and so is either `ZERO` or `ONE`.
+    pub fn trace(&self) -> Self {
+        (1..W)
+            .scan(*self, |acc, _| {
+                *acc = acc.square();
+                Some(*acc)
+            })
+            .fold(*self, |sum, v| sum + v)
+    }
+
+    /// Minimal polynomial over GF(2) i.e., the product of (X - c) over the distinct conjugates c.
+    ///
+    /// Its coefficients are all `ZERO` or `ONE` and its degree divides W.
+    pub fn min_poly(&self) -> Poly<Self> {
+        let mut res = Poly::linear(*self);
+        let mut conj = self.square();
+        while conj != *self {
+            res = &res * &Poly::linear(conj);
+            conj = conj.square();
+        }
+        res
+    }
+
+    /// Whether multiplication uses carry-less multiplication instead of the backend.
+    ///
+    /// Up to W = 16 the log tables fit in cache and a lookup beats the multiplication and
+    /// Barrett reduction (see `benches/field_ops.rs`). The constant-time backend is slower than
+    /// both, so it is always replaced when the instruction is available.
+    #[inline]
+    pub(crate) fn uses_clmul() -> bool {
+        (W > 16 || cfg!(feature = "constant-time")) && clmul::is_available()
+    }
+
+    /// Multiply using carry-less multiplication if [`GF::uses_clmul`] and using the backend
+    /// otherwise.
+    #[inline]
+    fn mul_raw(x: u32, y: u32) -> u32 {
+        Self::assert_supported();
+        if Self::uses_clmul() {
+            unsafe { clmul::mul_unchecked(x, y, W) }
+        } else {
+            backend::mul(x, y, W)
+        }
     }
 }
 
+/// Values that are not less than `ORDER` are reduced modulo the primitive polynomial, see
+/// [`GF::try_from_u32`] to reject them instead. Note that `TryFrom<u32>` is implemented through
+/// this conversion, so it reduces such values as well instead of returning an error.
 impl<const W: u8> From<u32> for GF<W> {
     fn from(value: u32) -> Self {
-        let res = Self(value);
-
         if value >= Self::ORDER {
-            res * Self::ONE
+            Self::new(native::reduce(value, W))
         } else {
-            res
+            Self::new(value)
         }
     }
 }
@@ -77,17 +231,13 @@ impl<const W: u8> From<GF<W>> for u32 {
 
 impl<const W: u8> MulAssign for GF<W> {
     fn mul_assign(&mut self, rhs: Self) {
-        self.0 = unsafe {
-            bindings::galois_logtable_multiply(self.0 as i32, rhs.0 as i32, W.into()) as u32
-        }
+        self.0 = Self::mul_raw(self.0, rhs.0);
     }
 }
 
 impl<const W: u8> MulAssign<&GF<W>> for GF<W> {
     fn mul_assign(&mut self, rhs: &Self) {
-        self.0 = unsafe {
-            bindings::galois_logtable_multiply(self.0 as i32, rhs.0 as i32, W.into()) as u32
-        }
+        self.0 = Self::mul_raw(self.0, rhs.0);
     }
 }
 
@@ -231,17 +381,13 @@ impl<const W: u8> Sub<GF<W>> for &GF<W> {
 
 impl<const W: u8> DivAssign for GF<W> {
     fn div_assign(&mut self, rhs: Self) {
-        self.0 = unsafe {
-            bindings::galois_logtable_divide(self.0 as i32, rhs.0 as i32, W.into()) as u32
-        };
+        *self = self.checked_div(rhs).expect("Division by zero.");
     }
 }
 
 impl<const W: u8> DivAssign<&GF<W>> for GF<W> {
     fn div_assign(&mut self, rhs: &Self) {
-        self.0 = unsafe {
-            bindings::galois_logtable_divide(self.0 as i32, rhs.0 as i32, W.into()) as u32
-        };
+        *self /= *rhs;
     }
 }
 
@@ -299,6 +445,35 @@ impl<const W: u8> One for GF<W> {
     }
 }
 
+impl<const W: u8> Field for GF<W> {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const NUM_BYTES: usize = Self::NUM_BYTES;
+    const WIDTH: usize = W as usize;
+    const CHARACTERISTIC: u128 = 2;
+    const DEGREE: usize = W as usize;
+
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        Self::rand(rng)
+    }
+
+    fn inv(&self) -> Option<Self> {
+        self.checked_inv()
+    }
+
+    fn dot(a: &[Self], b: &[Self]) -> Self {
+        dot(a, b)
+    }
+
+    fn mat_vec(m: ArrayView2<Self>, v: ArrayView1<Self>) -> Array1<Self> {
+        mat_vec(m, v)
+    }
+
+    fn mat_mul(a: ArrayView2<Self>, b: ArrayView2<Self>) -> Array2<Self> {
+        mat_mul(a, b)
+    }
+}
+
 impl<const W: u8> Serialize for GF<W> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -329,7 +504,12 @@ impl<'de, const W: u8> Visitor<'de> for GFVisitor<W> {
     type Value = GF<W>;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(formatter, "a sequence of {} u8 integers", (W + 7) / 8)
+        write!(
+            formatter,
+            "a sequence of {} u8 integers encoding a value less than 2^{}",
+            GF::<W>::NUM_BYTES,
+            W
+        )
     }
 
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -337,11 +517,21 @@ impl<'de, const W: u8> Visitor<'de> for GFVisitor<W> {
         A: serde::de::SeqAccess<'de>,
     {
         let mut vals = [0u8; 4];
-        for v in vals.iter_mut().take(GF::<W>::NUM_BYTES) {
-            let element = seq.next_element()?;
-            *v = element.unwrap();
+        for (i, v) in vals.iter_mut().take(GF::<W>::NUM_BYTES).enumerate() {
+            *v = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+
+        // The serialized bytes can hold values outside the field if W is not a multiple of 8.
+        let val = u32::from_le_bytes(vals);
+        if val >= GF::<W>::ORDER {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(val.into()),
+                &self,
+            ));
         }
 
-        Ok(GF(u32::from_le_bytes(vals)))
+        Ok(GF::new(val))
     }
 }
@@ -0,0 +1,192 @@
+//! Pure-Rust implementation of the field operations.
+//!
+//! Elements are represented in the polynomial basis modulo the same primitive polynomials as the
+//! C log tables, so results are identical to the C backend for every supported width.
+use std::sync::OnceLock;
+
+/// Largest supported field width.
+pub const MAX_WIDTH: u8 = 30;
+
+/// Widths up to this are multiplied using log/antilog tables.
+/// Larger widths fall back to shift-and-add since the tables would not fit in cache anyway.
+const MAX_TABLE_WIDTH: u8 = 16;
+
+/// Primitive polynomials indexed by field width (including the x^W term).
+/// Copied from the C library (in octal) so that both backends agree; the C library stores x + 1
+/// for W = 1 without the leading term.
+pub const PRIM_POLY: [u32; (MAX_WIDTH + 1) as usize] = [
+    0,
+    0o3,
+    0o7,
+    0o13,
+    0o23,
+    0o45,
+    0o103,
+    0o211,
+    0o435,
+    0o1021,
+    0o2011,
+    0o4005,
+    0o10123,
+    0o20033,
+    0o42103,
+    0o100003,
+    0o210013,
+    0o400011,
+    0o1000201,
+    0o2000047,
+    0o4000011,
+    0o10000005,
+    0o20000003,
+    0o40000041,
+    0o100000207,
+    0o200000011,
+    0o400000107,
+    0o1000000047,
+    0o2000000011,
+    0o4000000005,
+    0o10040000007,
+];
+
+struct LogTables {
+    log: Vec<u32>,
+    // Antilog table repeated twice so that sums and differences of logs need no reduction.
+    exp: Vec<u32>,
+}
+
+impl LogTables {
+    fn new(w: u8) -> Self {
+        let order = 1usize << w;
+        let mask = (order - 1) as u32;
+        let nwm1 = order - 1;
+
+        let mut log = vec![0; order];
+        let mut exp = vec![0; 2 * nwm1];
+
+        let mut b = 1u32;
+        for j in 0..nwm1 {
+            log[b as usize] = j as u32;
+            exp[j] = b;
+            exp[j + nwm1] = b;
+
+            b <<= 1;
+            if b as usize & order != 0 {
+                b = (b ^ PRIM_POLY[w as usize]) & mask;
+            }
+        }
+
+        Self { log, exp }
+    }
+}
+
+fn tables(w: u8) -> Option<&'static LogTables> {
+    static TABLES: [OnceLock<LogTables>; (MAX_TABLE_WIDTH + 1) as usize] =
+        [const { OnceLock::new() }; (MAX_TABLE_WIDTH + 1) as usize];
+
+    if w > MAX_TABLE_WIDTH {
+        return None;
+    }
+
+    Some(TABLES[w as usize].get_or_init(|| LogTables::new(w)))
+}
+
+/// Log and antilog tables for width `w`, if it is small enough to use them.
+/// The antilog table is repeated twice so that sums of logs can be looked up without reduction.
+pub(super) fn log_tables(w: u8) -> Option<(&'static [u32], &'static [u32])> {
+    tables(w).map(|t| (&t.log[..], &t.exp[..]))
+}
+
+/// Check that the width is supported and pre-compute the tables used for width `w`.
+pub fn init(w: u8) -> Result<(), &'static str> {
+    if w == 0 || w > MAX_WIDTH {
+        return Err("Could not initialize the field.");
+    }
+
+    tables(w);
+    Ok(())
+}
+
+/// Reduce an arbitrary 32-bit polynomial modulo the primitive polynomial of width `w`.
+pub fn reduce(mut x: u32, w: u8) -> u32 {
+    let poly = PRIM_POLY[w as usize];
+    for i in (w..32).rev() {
+        if (x >> i) & 1 == 1 {
+            x ^= poly << (i - w);
+        }
+    }
+    x
+}
+
+/// Multiply two field elements of width `w`.
+pub fn mul(x: u32, y: u32, w: u8) -> u32 {
+    if x == 0 || y == 0 {
+        return 0;
+    }
+
+    match tables(w) {
+        Some(t) => t.exp[(t.log[x as usize] + t.log[y as usize]) as usize],
+        None => shift_add_mul(x, y, w),
+    }
+}
+
+/// Divide two field elements of width `w`.
+///
+/// Like the C library, dividing by zero returns `u32::MAX` (i.e., -1).
+pub fn div(x: u32, y: u32, w: u8) -> u32 {
+    if y == 0 {
+        return u32::MAX;
+    }
+    if x == 0 {
+        return 0;
+    }
+
+    match tables(w) {
+        Some(t) => {
+            let nwm1 = (1u32 << w) - 1;
+            t.exp[(t.log[x as usize] + nwm1 - t.log[y as usize]) as usize]
+        }
+        None => shift_add_mul(x, euclid_inv(y, w), w),
+    }
+}
+
+fn shift_add_mul(mut x: u32, mut y: u32, w: u8) -> u32 {
+    let poly = PRIM_POLY[w as usize];
+    let mut res = 0;
+
+    while y != 0 {
+        if y & 1 == 1 {
+            res ^= x;
+        }
+        y >>= 1;
+
+        x <<= 1;
+        if (x >> w) & 1 == 1 {
+            x ^= poly;
+        }
+    }
+
+    res
+}
+
+/// Inverse of a non-zero element using the extended Euclidean algorithm over GF(2)[x].
+fn euclid_inv(x: u32, w: u8) -> u32 {
+    let deg = |v: u64| 63 - v.leading_zeros() as i32;
+
+    let (mut u, mut v) = (x as u64, PRIM_POLY[w as usize] as u64);
+    let (mut g1, mut g2) = (1u64, 0u64);
+
+    // Invariant: x * g1 = u and x * g2 = v modulo the primitive polynomial.
+    while u != 1 {
+        let mut j = deg(u) - deg(v);
+        if j < 0 {
+            std::mem::swap(&mut u, &mut v);
+            std::mem::swap(&mut g1, &mut g2);
+            j = -j;
+        }
+
+        u ^= v << j;
+        g1 ^= g2 << j;
+    }
+
+    g1 as u32
+}
@@ -0,0 +1,181 @@
+//! Dense serialization of vectors and matrices over GF(2^W) using exactly W bits per element.
+//!
+//! The serde implementation of `GF<W>` uses whole bytes per element, which wastes up to 7 bits
+//! per element. Here element i occupies bits i * W to (i + 1) * W - 1 of the output, where bit j
+//! is bit j % 8 of byte j / 8, and the unused bits of the last byte are zero.
+use super::GF;
+use ndarray::{Array1, Array2};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Number of bytes needed to pack `n` elements of width `W`.
+pub fn packed_len<const W: u8>(n: usize) -> usize {
+    (n * W as usize).div_ceil(8)
+}
+
+/// Packs elements using exactly W bits each.
+pub fn pack<const W: u8>(vals: &[GF<W>]) -> Vec<u8> {
+    let mut res = Vec::with_capacity(packed_len::<W>(vals.len()));
+
+    // Bits that have not been written yet, starting from the lowest.
+    let mut acc = 0u64;
+    let mut num_bits = 0;
+    for v in vals {
+        acc |= (v.0 as u64) << num_bits;
+        num_bits += W as u32;
+        while num_bits >= 8 {
+            res.push(acc as u8);
+            acc >>= 8;
+            num_bits -= 8;
+        }
+    }
+
+    if num_bits > 0 {
+        res.push(acc as u8);
+    }
+
+    res
+}
+
+/// Unpacks `n` elements packed using [`pack`].
+///
+/// Fails if the length of `bytes` doesn't match or if the unused bits are not zero, so that every
+/// element has a unique encoding.
+pub fn unpack<const W: u8>(bytes: &[u8], n: usize) -> Result<Vec<GF<W>>, &'static str> {
+    // The number of elements might come from untrusted input, so avoid overflows.
+    if n.checked_mul(W as usize).map(|v| v.div_ceil(8)) != Some(bytes.len()) {
+        return Err("Packed length doesn't match the number of elements.");
+    }
+
+    let mask = (1u64 << W) - 1;
+    let mut res = Vec::with_capacity(n);
+    let mut bytes = bytes.iter();
+
+    let mut acc = 0u64;
+    let mut num_bits = 0;
+    for _ in 0..n {
+        while num_bits < W as u32 {
+            acc |= (*bytes.next().unwrap() as u64) << num_bits;
+            num_bits += 8;
+        }
+
+        res.push(GF::new((acc & mask) as u32));
+        acc >>= W;
+        num_bits -= W as u32;
+    }
+
+    if acc != 0 {
+        return Err("Padding bits should be zero.");
+    }
+
+    Ok(res)
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireVec {
+    len: u64,
+    bytes: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireMatrix {
+    rows: u64,
+    cols: u64,
+    bytes: Vec<u8>,
+}
+
+/// Vector of field elements that is serialized using W bits per element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PackedVec<const W: u8>(pub Vec<GF<W>>);
+
+impl<const W: u8> From<Vec<GF<W>>> for PackedVec<W> {
+    fn from(value: Vec<GF<W>>) -> Self {
+        Self(value)
+    }
+}
+
+impl<const W: u8> From<Array1<GF<W>>> for PackedVec<W> {
+    fn from(value: Array1<GF<W>>) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+impl<const W: u8> From<PackedVec<W>> for Array1<GF<W>> {
+    fn from(value: PackedVec<W>) -> Self {
+        Array1::from_vec(value.0)
+    }
+}
+
+impl<const W: u8> Serialize for PackedVec<W> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        WireVec {
+            len: self.0.len() as u64,
+            bytes: pack(&self.0),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, const W: u8> Deserialize<'de> for PackedVec<W> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let wire = WireVec::deserialize(deserializer)?;
+        let len = usize::try_from(wire.len).map_err(D::Error::custom)?;
+        unpack(&wire.bytes, len).map(Self).map_err(D::Error::custom)
+    }
+}
+
+/// Matrix of field elements that is serialized using W bits per element in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PackedMatrix<const W: u8>(pub Array2<GF<W>>);
+
+impl<const W: u8> From<Array2<GF<W>>> for PackedMatrix<W> {
+    fn from(value: Array2<GF<W>>) -> Self {
+        Self(value)
+    }
+}
+
+impl<const W: u8> From<PackedMatrix<W>> for Array2<GF<W>> {
+    fn from(value: PackedMatrix<W>) -> Self {
+        value.0
+    }
+}
+
+impl<const W: u8> Serialize for PackedMatrix<W> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let entries: Vec<_> = self.0.iter().cloned().collect();
+        WireMatrix {
+            rows: self.0.nrows() as u64,
+            cols: self.0.ncols() as u64,
+            bytes: pack(&entries),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, const W: u8> Deserialize<'de> for PackedMatrix<W> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let wire = WireMatrix::deserialize(deserializer)?;
+        let shape = usize::try_from(wire.rows)
+            .and_then(|rows| Ok((rows, usize::try_from(wire.cols)?)))
+            .map_err(D::Error::custom)?;
+        let len = shape
+            .0
+            .checked_mul(shape.1)
+            .ok_or_else(|| D::Error::custom("Dimensions are too large."))?;
+
+        let entries = unpack(&wire.bytes, len).map_err(D::Error::custom)?;
+        Ok(Self(Array2::from_shape_vec(shape, entries).unwrap()))
+    }
+}
@@ -0,0 +1,360 @@
+//! Binary extension fields GF(2^64) and GF(2^128).
+//!
+//! These are too large for log tables, so products are computed with carry-less multiplication,
+//! using PCLMULQDQ when the CPU supports it, and reduced modulo a sparse irreducible polynomial.
+//! Elements of `GF<W>` can be embedded into these fields using `lift` whenever W divides the
+//! width of the larger field, so that sharings can be checked in the large field.
+use super::native::{MAX_WIDTH, PRIM_POLY};
+use super::{clmul, GF};
+use crate::math::poly::Poly;
+use crate::math::Field;
+use num_traits::identities::{One, Zero};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::sync::OnceLock;
+
+/// Galois field elements where the order of the field is 2^64.
+///
+/// Elements are polynomials modulo x^64 + x^4 + x^3 + x + 1.
+///
+/// Only `GF<W>` with W in 1, 2, 4, 8 and 16 can be lifted into this field, since GF(2^W) is a
+/// subfield exactly when W divides 64. Lifting any other width, e.g., `GF<12>`, fails to compile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GF64(u64);
+
+/// Galois field elements where the order of the field is 2^128.
+///
+/// Elements are polynomials modulo x^128 + x^7 + x^2 + x + 1.
+///
+/// Only `GF<W>` with W in 1, 2, 4, 8 and 16 can be lifted into this field, since GF(2^W) is a
+/// subfield exactly when W divides 128. Lifting any other width, e.g., `GF<12>`, fails to compile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GF128(u128);
+
+/// Full carry-less product of two 64-bit polynomials.
+#[inline]
+fn clmul64(a: u64, b: u64) -> u128 {
+    if clmul::is_available() {
+        unsafe { clmul::clmul_wide(a, b) }
+    } else {
+        (0..64)
+            .filter(|i| (b >> i) & 1 == 1)
+            .fold(0, |acc, i| acc ^ ((a as u128) << i))
+    }
+}
+
+impl GF64 {
+    /// Width of the field.
+    pub const WIDTH: usize = 64;
+
+    /// Number of bytes when serialized.
+    pub const NUM_BYTES: usize = 8;
+
+    /// Additive identity.
+    pub const ZERO: Self = Self(0);
+
+    /// Multiplicative identity.
+    pub const ONE: Self = Self(1);
+
+    /// Sample an element uniformly at random from the field.
+    pub fn rand<R: Rng>(rng: &mut R) -> Self {
+        Self(rng.gen())
+    }
+
+    /// Embed an element of GF(2^W) into this field.
+    ///
+    /// The embedding is a field homomorphism so sums and products are preserved.
+    /// W must divide 64, which is checked at compile time.
+    pub fn lift<const W: u8>(x: GF<W>) -> Self {
+        const {
+            assert!(
+                64 % W == 0,
+                "GF<W> is a subfield of GF64 only if W divides 64"
+            )
+        };
+
+        static BASES: [OnceLock<Vec<GF64>>; (MAX_WIDTH + 1) as usize] =
+            [const { OnceLock::new() }; (MAX_WIDTH + 1) as usize];
+
+        let basis = BASES[W as usize].get_or_init(|| subfield_basis(W, Self::WIDTH));
+        lift_with(basis, x.0)
+    }
+
+    #[inline]
+    fn mul_raw(x: u64, y: u64) -> u64 {
+        Self::reduce(clmul64(x, y))
+    }
+
+    // Reduce modulo x^64 + x^4 + x^3 + x + 1 i.e., using x^64 = x^4 + x^3 + x + 1.
+    #[inline]
+    fn reduce(prod: u128) -> u64 {
+        let fold = |hi: u128| hi ^ (hi << 1) ^ (hi << 3) ^ (hi << 4);
+
+        // The upper half folds into at most 68 bits, whose top 4 bits are folded once more.
+        let t = fold(prod >> 64);
+        let lo = (prod as u64) ^ (t as u64);
+        lo ^ (fold(t >> 64) as u64)
+    }
+}
+
+impl GF128 {
+    /// Width of the field.
+    pub const WIDTH: usize = 128;
+
+    /// Number of bytes when serialized.
+    pub const NUM_BYTES: usize = 16;
+
+    /// Additive identity.
+    pub const ZERO: Self = Self(0);
+
+    /// Multiplicative identity.
+    pub const ONE: Self = Self(1);
+
+    /// Sample an element uniformly at random from the field.
+    pub fn rand<R: Rng>(rng: &mut R) -> Self {
+        Self(rng.gen())
+    }
+
+    /// Embed an element of GF(2^W) into this field.
+    ///
+    /// The embedding is a field homomorphism so sums and products are preserved.
+    /// W must divide 128, which is checked at compile time.
+    pub fn lift<const W: u8>(x: GF<W>) -> Self {
+        const {
+            assert!(
+                128 % W == 0,
+                "GF<W> is a subfield of GF128 only if W divides 128"
+            )
+        };
+
+        static BASES: [OnceLock<Vec<GF128>>; (MAX_WIDTH + 1) as usize] =
+            [const { OnceLock::new() }; (MAX_WIDTH + 1) as usize];
+
+        let basis = BASES[W as usize].get_or_init(|| subfield_basis(W, Self::WIDTH));
+        lift_with(basis, x.0)
+    }
+
+    // Reduce modulo x^128 + x^7 + x^2 + x + 1 i.e., using x^128 = x^7 + x^2 + x + 1.
+    #[inline]
+    fn reduce(hi: u128, lo: u128) -> u128 {
+        let fold = |v: u128| v ^ (v << 1) ^ (v << 2) ^ (v << 7);
+
+        // Bits of the upper half that overflow when folded, at most 7 of them.
+        let overflow = (hi >> 127) ^ (hi >> 126) ^ (hi >> 121);
+        lo ^ fold(hi) ^ fold(overflow)
+    }
+
+    #[inline]
+    fn mul_raw(x: u128, y: u128) -> u128 {
+        let (x0, x1) = (x as u64, (x >> 64) as u64);
+        let (y0, y1) = (y as u64, (y >> 64) as u64);
+
+        let lo = clmul64(x0, y0);
+        let hi = clmul64(x1, y1);
+        let mid = clmul64(x0, y1) ^ clmul64(x1, y0);
+
+        Self::reduce(hi ^ (mid >> 64), lo ^ (mid << 64))
+    }
+}
+
+/// Images of 1, x, ..., x^(W-1) under an embedding of GF(2^W) into a field of width `k`.
+///
+/// The image of x is a root of the primitive polynomial of GF(2^W) in the larger field. The root
+/// is found with a fixed seed so that every party computes the same embedding.
+fn subfield_basis<F: Field>(w: u8, k: usize) -> Vec<F> {
+    let poly = PRIM_POLY[w as usize];
+    let poly = Poly::new(
+        (0..=w)
+            .map(|i| {
+                if (poly >> i) & 1 == 1 {
+                    F::ONE
+                } else {
+                    F::ZERO
+                }
+            })
+            .collect(),
+    );
+
+    let mut rng = ChaCha12Rng::seed_from_u64(w.into());
+    let root = find_root(poly, k, &mut rng);
+
+    std::iter::successors(Some(F::ONE), |&v| Some(v * root))
+        .take(w.into())
+        .collect()
+}
+
+fn lift_with<F: Field>(basis: &[F], x: u32) -> F {
+    basis
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| (x >> i) & 1 == 1)
+        .fold(F::ZERO, |acc, (_, &v)| acc + v)
+}
+
+/// Finds a root of a polynomial that splits into distinct linear factors over a binary field of
+/// width `k`.
+///
+/// Uses the trace map Tr(y) = y + y^2 + ... + y^(2^(k-1)) which is GF(2)-valued. For random
+/// `delta`, gcd(p(x), Tr(delta * x)) separates the roots depending on the value of
+/// Tr(delta * root), which splits the polynomial with probability about 1/2.
+fn find_root<F: Field, R: Rng>(mut poly: Poly<F>, k: usize, rng: &mut R) -> F {
+    while poly.degree() > Some(1) {
+        let (_, mut s) = Poly::new(vec![F::ZERO, F::rand(rng)]).div_rem(&poly);
+        let mut trace = s.clone();
+        for _ in 1..k {
+            s = (&s * &s).div_rem(&poly).1;
+            trace = &trace + &s;
+        }
+
+        let g = poly.gcd(&trace);
+        if g.degree() > Some(0) && g.degree() < poly.degree() {
+            poly = g;
+        }
+    }
+
+    let coeffs = poly.coeffs();
+    F::ZERO - coeffs[0] / coeffs[1]
+}
+
+/// Implements the field operations given the bit representation `$repr` and a function
+/// `$mul` that multiplies two such representations.
+macro_rules! impl_binary_field {
+    ($name:ident, $repr:ty, $mul:path) => {
+        impl $name {
+            /// Multiplicative inverse, or `None` for zero.
+            pub fn inv(&self) -> Option<Self> {
+                if self.0 == 0 {
+                    return None;
+                }
+
+                // x^(2^k - 2) = x^2 * x^4 * ... * x^(2^(k-1)).
+                let mut res = Self::ONE;
+                let mut sq = *self;
+                for _ in 1..Self::WIDTH {
+                    sq *= sq;
+                    res *= sq;
+                }
+                Some(res)
+            }
+        }
+
+        impl From<u32> for $name {
+            fn from(value: u32) -> Self {
+                Self(value.into())
+            }
+        }
+
+        impl From<$repr> for $name {
+            fn from(value: $repr) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $repr {
+            fn from(value: $name) -> $repr {
+                value.0
+            }
+        }
+
+        impl MulAssign for $name {
+            fn mul_assign(&mut self, rhs: Self) {
+                self.0 = $mul(self.0, rhs.0);
+            }
+        }
+
+        impl Mul for $name {
+            type Output = Self;
+
+            fn mul(mut self, other: Self) -> Self {
+                self *= other;
+                self
+            }
+        }
+
+        #[allow(clippy::suspicious_op_assign_impl)]
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 ^= rhs.0;
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(mut self, other: Self) -> Self {
+                self += other;
+                self
+            }
+        }
+
+        #[allow(clippy::suspicious_op_assign_impl)]
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self += rhs;
+            }
+        }
+
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                self + other
+            }
+        }
+
+        #[allow(clippy::suspicious_op_assign_impl)]
+        impl DivAssign for $name {
+            fn div_assign(&mut self, rhs: Self) {
+                *self *= rhs.inv().expect("division by zero");
+            }
+        }
+
+        impl Div for $name {
+            type Output = Self;
+
+            fn div(mut self, rhs: Self) -> Self {
+                self /= rhs;
+                self
+            }
+        }
+
+        impl Zero for $name {
+            fn zero() -> Self {
+                Self::ZERO
+            }
+
+            fn is_zero(&self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl One for $name {
+            fn one() -> Self {
+                Self::ONE
+            }
+        }
+
+        impl Field for $name {
+            const ZERO: Self = Self::ZERO;
+            const ONE: Self = Self::ONE;
+            const NUM_BYTES: usize = Self::NUM_BYTES;
+            const WIDTH: usize = Self::WIDTH;
+            const CHARACTERISTIC: u128 = 2;
+            const DEGREE: usize = Self::WIDTH;
+
+            fn rand<R: Rng>(rng: &mut R) -> Self {
+                Self::rand(rng)
+            }
+
+            fn inv(&self) -> Option<Self> {
+                self.inv()
+            }
+        }
+    };
+}
+
+impl_binary_field!(GF64, u64, GF64::mul_raw);
+impl_binary_field!(GF128, u128, GF128::mul_raw);
@@ -0,0 +1,322 @@
+//! Linear algebra over finite fields using Gaussian elimination.
+//!
+//! These are mainly used to check that generated or loaded matrices have the properties they are
+//! expected to have, e.g., that a square matrix is invertible or that every required square
+//! submatrix of a super-invertible or hyper-invertible matrix is invertible. Pivots are required
+//! to be invertible, so over Galois rings the results only describe the matrix when elimination
+//! never runs into a column whose non-zero entries are all non-units.
+use super::Field;
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, Axis};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use rayon::prelude::*;
+
+/// Reduced row echelon form of a matrix along with bookkeeping from the elimination.
+struct Echelon<F: Field> {
+    mat: Array2<F>,
+    // Column of the pivot in each of the first `pivots.len()` rows.
+    pivots: Vec<usize>,
+    // Product of the factors that the determinant was divided by during elimination.
+    scale: F,
+}
+
+/// Reduce `mat` to reduced row echelon form, eliminating only the first `num_cols` columns.
+fn echelon<F: Field>(mut mat: Array2<F>, num_cols: usize) -> Echelon<F> {
+    let num_rows = mat.nrows();
+    let mut pivots = Vec::new();
+    let mut scale = F::ONE;
+
+    for col in 0..num_cols {
+        let rank = pivots.len();
+        if rank == num_rows {
+            break;
+        }
+
+        let Some(prow) = (rank..num_rows).find(|&r| mat[[r, col]].inv().is_some()) else {
+            continue;
+        };
+
+        if prow != rank {
+            for c in col..mat.ncols() {
+                mat.swap([prow, c], [rank, c]);
+            }
+            scale = F::ZERO - scale;
+        }
+
+        let pinv = mat[[rank, col]].inv().unwrap();
+        mat.row_mut(rank).mapv_inplace(|v| v * pinv);
+        scale *= pinv;
+
+        let prow = mat.row(rank).to_owned();
+        mat.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .enumerate()
+            .filter(|&(r, _)| r != rank)
+            .for_each(|(_, mut row)| {
+                let factor = row[col];
+                if !factor.is_zero() {
+                    row.zip_mut_with(&prow, |v, &p| *v -= factor * p);
+                }
+            });
+
+        pivots.push(col);
+    }
+
+    Echelon { mat, pivots, scale }
+}
+
+/// Rank of a matrix.
+pub fn rank<F: Field>(mat: ArrayView2<F>) -> usize {
+    echelon(mat.to_owned(), mat.ncols()).pivots.len()
+}
+
+/// Determinant of a square matrix.
+pub fn determinant<F: Field>(mat: ArrayView2<F>) -> F {
+    assert_eq!(mat.nrows(), mat.ncols(), "Matrix should be square.");
+
+    let ech = echelon(mat.to_owned(), mat.ncols());
+    if ech.pivots.len() < mat.nrows() {
+        return F::ZERO;
+    }
+
+    // The reduced form is the identity, whose determinant is one.
+    ech.scale.inv().unwrap()
+}
+
+/// Inverse of a square matrix, or `None` if it is singular.
+pub fn inverse<F: Field>(mat: ArrayView2<F>) -> Option<Array2<F>> {
+    let n = mat.nrows();
+    assert_eq!(n, mat.ncols(), "Matrix should be square.");
+
+    let mut aug = Array2::from_elem((n, 2 * n), F::ZERO);
+    aug.slice_mut(s![.., ..n]).assign(&mat);
+    for i in 0..n {
+        aug[[i, n + i]] = F::ONE;
+    }
+
+    let ech = echelon(aug, n);
+    if ech.pivots.len() < n {
+        return None;
+    }
+
+    Some(ech.mat.slice(s![.., n..]).to_owned())
+}
+
+/// Some solution x to `mat * x = rhs`, or `None` if the system is inconsistent.
+///
+/// When the system has multiple solutions, the free variables are set to zero.
+pub fn solve<F: Field>(mat: ArrayView2<F>, rhs: ArrayView1<F>) -> Option<Array1<F>> {
+    let (num_rows, num_cols) = mat.dim();
+    assert_eq!(num_rows, rhs.len(), "Dimension mismatch.");
+
+    let mut aug = Array2::from_elem((num_rows, num_cols + 1), F::ZERO);
+    aug.slice_mut(s![.., ..num_cols]).assign(&mat);
+    aug.column_mut(num_cols).assign(&rhs);
+
+    let ech = echelon(aug, num_cols);
+    let rank = ech.pivots.len();
+    if ech
+        .mat
+        .slice(s![rank.., num_cols])
+        .iter()
+        .any(|v| !v.is_zero())
+    {
+        return None;
+    }
+
+    let mut sol = Array1::from_elem(num_cols, F::ZERO);
+    for (r, &c) in ech.pivots.iter().enumerate() {
+        sol[c] = ech.mat[[r, num_cols]];
+    }
+    Some(sol)
+}
+
+/// Basis of the null space {x : mat * x = 0}, with one basis vector per row of the output.
+pub fn kernel<F: Field>(mat: ArrayView2<F>) -> Array2<F> {
+    let num_cols = mat.ncols();
+    let ech = echelon(mat.to_owned(), num_cols);
+
+    // Each free column gives a basis vector where that variable is one, the other free variables
+    // are zero and the pivot variables are set to cancel it out.
+    let free: Vec<_> = (0..num_cols).filter(|c| !ech.pivots.contains(c)).collect();
+
+    let mut basis = Array2::from_elem((free.len(), num_cols), F::ZERO);
+    for (mut vec, &fc) in basis.outer_iter_mut().zip(free.iter()) {
+        vec[fc] = F::ONE;
+        for (r, &pc) in ech.pivots.iter().enumerate() {
+            vec[pc] = F::ZERO - ech.mat[[r, fc]];
+        }
+    }
+
+    basis
+}
+
+/// Submatrix given by its row and column indices in increasing order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Minor {
+    pub rows: Vec<usize>,
+    pub cols: Vec<usize>,
+}
+
+impl std::fmt::Display for Minor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "rows {:?}, columns {:?}", self.rows, self.cols)
+    }
+}
+
+/// How many square submatrices to check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinorCheck {
+    /// Check every required submatrix, which is only feasible for small matrices since their
+    /// number grows exponentially.
+    Exact,
+    /// Check `trials` required submatrices chosen uniformly at random using the given seed.
+    Randomized { trials: usize, seed: u64 },
+}
+
+/// Checks that a matrix is super-invertible i.e., that every square submatrix formed by all rows
+/// and any subset of columns is invertible, which is the case for `super_inv_matrix`.
+///
+/// For matrices with more rows than columns, all columns and subsets of rows are used instead.
+/// Returns the first failing submatrix found.
+pub fn check_super_invertible<F: Field>(mat: ArrayView2<F>, mode: MinorCheck) -> Result<(), Minor> {
+    let (num_rows, num_cols) = mat.dim();
+    check_super_invertible_for(mat, num_rows.min(num_cols), mode)
+}
+
+/// Checks the relaxed form of super-invertibility where every submatrix formed by all rows and
+/// any `subset_size` columns has full rank, i.e., any `subset_size` inputs determine a uniformly
+/// random output when the matrix is used for randomness extraction.
+///
+/// As in [`check_super_invertible`], the roles of rows and columns are swapped for matrices with
+/// more rows than columns.
+pub fn check_super_invertible_for<F: Field>(
+    mat: ArrayView2<F>,
+    subset_size: usize,
+    mode: MinorCheck,
+) -> Result<(), Minor> {
+    let (num_rows, num_cols) = mat.dim();
+    let (shorter, longer) = (num_rows.min(num_cols), num_rows.max(num_cols));
+    assert!(
+        shorter <= subset_size && subset_size <= longer,
+        "Subset size should lie between the dimensions of the matrix."
+    );
+
+    // Expand a subset of the longer dimension into a submatrix.
+    let minor = |subset: Vec<usize>| {
+        if num_rows <= num_cols {
+            Minor {
+                rows: (0..shorter).collect(),
+                cols: subset,
+            }
+        } else {
+            Minor {
+                rows: subset,
+                cols: (0..shorter).collect(),
+            }
+        }
+    };
+
+    match mode {
+        MinorCheck::Exact => Subsets::new(longer, subset_size)
+            .map(minor)
+            .try_for_each(|m| check_minor(mat, m)),
+        MinorCheck::Randomized { trials, seed } => {
+            let mut rng = ChaCha12Rng::seed_from_u64(seed);
+            (0..trials).try_for_each(|_| {
+                check_minor(mat, minor(rand_subset(longer, subset_size, &mut rng)))
+            })
+        }
+    }
+}
+
+/// Checks that a matrix is hyper-invertible i.e., that every square submatrix formed by any
+/// subset of rows and any subset of columns of the same size is invertible.
+///
+/// Returns the first failing submatrix found, checking smaller submatrices first in exact mode.
+pub fn check_hyper_invertible<F: Field>(mat: ArrayView2<F>, mode: MinorCheck) -> Result<(), Minor> {
+    let (num_rows, num_cols) = mat.dim();
+    let max_size = num_rows.min(num_cols);
+
+    match mode {
+        MinorCheck::Exact => (1..=max_size).try_for_each(|k| {
+            Subsets::new(num_rows, k).try_for_each(|rows| {
+                Subsets::new(num_cols, k).try_for_each(|cols| {
+                    check_minor(
+                        mat,
+                        Minor {
+                            rows: rows.clone(),
+                            cols,
+                        },
+                    )
+                })
+            })
+        }),
+        MinorCheck::Randomized { trials, seed } => {
+            if max_size == 0 {
+                return Ok(());
+            }
+
+            let mut rng = ChaCha12Rng::seed_from_u64(seed);
+            (0..trials).try_for_each(|_| {
+                let k = rng.gen_range(1..=max_size);
+                let rows = rand_subset(num_rows, k, &mut rng);
+                let cols = rand_subset(num_cols, k, &mut rng);
+                check_minor(mat, Minor { rows, cols })
+            })
+        }
+    }
+}
+
+fn check_minor<F: Field>(mat: ArrayView2<F>, minor: Minor) -> Result<(), Minor> {
+    let sub = mat
+        .select(Axis(0), &minor.rows)
+        .select(Axis(1), &minor.cols);
+    if rank(sub.view()) == minor.rows.len().min(minor.cols.len()) {
+        Ok(())
+    } else {
+        Err(minor)
+    }
+}
+
+fn rand_subset<R: Rng>(n: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    let mut subset = rand::seq::index::sample(rng, n, k).into_vec();
+    subset.sort_unstable();
+    subset
+}
+
+/// Subsets of {0, ..., n - 1} of size k in lexicographic order.
+struct Subsets {
+    n: usize,
+    next: Option<Vec<usize>>,
+}
+
+impl Subsets {
+    fn new(n: usize, k: usize) -> Self {
+        Self {
+            n,
+            next: (k <= n).then(|| (0..k).collect()),
+        }
+    }
+}
+
+impl Iterator for Subsets {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cur = self.next.take()?;
+        let k = cur.len();
+
+        // Increment the last index that is not at its maximum and reset the ones after it.
+        let mut succ = cur.clone();
+        if let Some(i) = (0..k).rev().find(|&i| succ[i] < self.n - k + i) {
+            succ[i] += 1;
+            for j in (i + 1)..k {
+                succ[j] = succ[j - 1] + 1;
+            }
+            self.next = Some(succ);
+        }
+
+        Some(cur)
+    }
+}
@@ -0,0 +1,278 @@
+//! Versioned binary container for pre-computed matrices.
+//!
+//! A file consists of a header followed by the entries in row-major order, each serialized using
+//! `NUM_BYTES` bytes. All integers in the header are little endian:
+//!
+//! | bytes | contents                                          |
+//! |-------|---------------------------------------------------|
+//! | 4     | magic bytes `PSSM`                                |
+//! | 2     | format version                                    |
+//! | 1     | [`MatrixKind`]                                    |
+//! | 1     | reserved, zero                                    |
+//! | 16    | characteristic i.e., `Field::CHARACTERISTIC`      |
+//! | 4     | degree i.e., `Field::DEGREE`                      |
+//! | 4     | width of the field i.e., `Field::WIDTH`           |
+//! | 4     | bytes per entry i.e., `Field::NUM_BYTES`          |
+//! | 4     | reserved, zero                                    |
+//! | 8     | number of rows                                    |
+//! | 8     | number of columns                                 |
+//! | 8     | seahash checksum of the entries                   |
+use super::Field;
+use ndarray::{Array2, ArrayView2};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: [u8; 4] = *b"PSSM";
+const VERSION: u16 = 1;
+const HEADER_LEN: usize = 64;
+
+/// What a stored matrix is meant to be used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatrixKind {
+    Generic = 0,
+    SuperInvertible = 1,
+    BinarySuperInvertible = 2,
+    HyperInvertible = 3,
+    RsGenerator = 4,
+    Lagrange = 5,
+}
+
+impl TryFrom<u8> for MatrixKind {
+    type Error = MatrixFileError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Generic),
+            1 => Ok(Self::SuperInvertible),
+            2 => Ok(Self::BinarySuperInvertible),
+            3 => Ok(Self::HyperInvertible),
+            4 => Ok(Self::RsGenerator),
+            5 => Ok(Self::Lagrange),
+            _ => Err(MatrixFileError::Malformed("Unknown matrix kind.")),
+        }
+    }
+}
+
+/// Parameters identifying the field (or ring) of the entries of a stored matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldId {
+    pub characteristic: u128,
+    pub degree: usize,
+    pub width: usize,
+    pub num_bytes: usize,
+}
+
+impl FieldId {
+    /// Parameters of the field `F`.
+    pub fn of<F: Field>() -> Self {
+        Self {
+            characteristic: F::CHARACTERISTIC,
+            degree: F::DEGREE,
+            width: F::WIDTH,
+            num_bytes: F::NUM_BYTES,
+        }
+    }
+}
+
+impl fmt::Display for FieldId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "characteristic {} and degree {} ({} bits in {} bytes)",
+            self.characteristic, self.degree, self.width, self.num_bytes
+        )
+    }
+}
+
+/// Errors when reading or writing stored matrices.
+#[derive(Debug)]
+pub enum MatrixFileError {
+    Io(io::Error),
+    /// Entry of a text file that is not allowed, with 1-based line number.
+    InvalidEntry {
+        line: usize,
+        entry: String,
+    },
+    /// Line of a text file with a different number of entries than the first line.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// File without any entries.
+    Empty,
+    /// Header does not start with the expected magic bytes.
+    BadMagic,
+    UnsupportedVersion(u16),
+    KindMismatch {
+        expected: MatrixKind,
+        found: MatrixKind,
+    },
+    /// Entries belong to a different field.
+    FieldMismatch {
+        expected: FieldId,
+        found: FieldId,
+    },
+    ChecksumMismatch,
+    Malformed(&'static str),
+}
+
+impl fmt::Display for MatrixFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "I/O error: {}", err),
+            Self::InvalidEntry { line, entry } => {
+                write!(f, "invalid entry {:?} on line {}", entry, line)
+            }
+            Self::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} has {} entries instead of {}",
+                line, found, expected
+            ),
+            Self::Empty => write!(f, "matrix has no entries"),
+            Self::BadMagic => write!(f, "not a matrix file"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported format version {}", v),
+            Self::KindMismatch { expected, found } => {
+                write!(f, "expected a {:?} matrix but found {:?}", expected, found)
+            }
+            Self::FieldMismatch { expected, found } => {
+                write!(f, "expected entries with {} but found {}", expected, found)
+            }
+            Self::ChecksumMismatch => write!(f, "checksum mismatch"),
+            Self::Malformed(msg) => write!(f, "malformed matrix file: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MatrixFileError {}
+
+impl From<io::Error> for MatrixFileError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Writes a matrix in the binary container format.
+pub fn write_matrix<F: Field, W: Write>(
+    writer: &mut W,
+    matrix: ArrayView2<F>,
+    kind: MatrixKind,
+) -> Result<(), MatrixFileError> {
+    let mut payload = Vec::with_capacity(matrix.len() * F::NUM_BYTES);
+    for v in matrix.iter() {
+        bincode::serialize_into(&mut payload, v)
+            .map_err(|_| MatrixFileError::Malformed("Failed to serialize entry."))?;
+    }
+
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(&MAGIC);
+    header.extend_from_slice(&VERSION.to_le_bytes());
+    header.push(kind as u8);
+    header.push(0);
+    header.extend_from_slice(&F::CHARACTERISTIC.to_le_bytes());
+    header.extend_from_slice(&(F::DEGREE as u32).to_le_bytes());
+    header.extend_from_slice(&(F::WIDTH as u32).to_le_bytes());
+    header.extend_from_slice(&(F::NUM_BYTES as u32).to_le_bytes());
+    header.extend_from_slice(&[0; 4]);
+    header.extend_from_slice(&(matrix.nrows() as u64).to_le_bytes());
+    header.extend_from_slice(&(matrix.ncols() as u64).to_le_bytes());
+    header.extend_from_slice(&seahash::hash(&payload).to_le_bytes());
+
+    writer.write_all(&header)?;
+    writer.write_all(&payload)?;
+    Ok(())
+}
+
+/// Reads a matrix in the binary container format, checking that it has the expected kind and
+/// belongs to the field `F`.
+pub fn read_matrix<F: Field, R: Read>(
+    reader: &mut R,
+    kind: MatrixKind,
+) -> Result<Array2<F>, MatrixFileError> {
+    let mut header = [0u8; HEADER_LEN];
+    reader.read_exact(&mut header)?;
+
+    let u32_at = |i: usize| u32::from_le_bytes(header[i..(i + 4)].try_into().unwrap());
+    let u64_at = |i: usize| u64::from_le_bytes(header[i..(i + 8)].try_into().unwrap());
+    let u128_at = |i: usize| u128::from_le_bytes(header[i..(i + 16)].try_into().unwrap());
+
+    if header[..4] != MAGIC {
+        return Err(MatrixFileError::BadMagic);
+    }
+
+    let version = u16::from_le_bytes([header[4], header[5]]);
+    if version != VERSION {
+        return Err(MatrixFileError::UnsupportedVersion(version));
+    }
+
+    let found = MatrixKind::try_from(header[6])?;
+    if found != kind {
+        return Err(MatrixFileError::KindMismatch {
+            expected: kind,
+            found,
+        });
+    }
+
+    let field = FieldId {
+        characteristic: u128_at(8),
+        degree: u32_at(24) as usize,
+        width: u32_at(28) as usize,
+        num_bytes: u32_at(32) as usize,
+    };
+    if field != FieldId::of::<F>() {
+        return Err(MatrixFileError::FieldMismatch {
+            expected: FieldId::of::<F>(),
+            found: field,
+        });
+    }
+
+    let (num_rows, num_cols) = (u64_at(40), u64_at(48));
+    let len = num_rows
+        .checked_mul(num_cols)
+        .and_then(|v| v.checked_mul(F::NUM_BYTES as u64))
+        .and_then(|v| usize::try_from(v).ok())
+        .ok_or(MatrixFileError::Malformed("Dimensions are too large."))?;
+
+    let mut payload = Vec::new();
+    reader.take(len as u64).read_to_end(&mut payload)?;
+    if payload.len() != len {
+        return Err(MatrixFileError::Malformed("File is truncated."));
+    }
+    if seahash::hash(&payload) != u64_at(56) {
+        return Err(MatrixFileError::ChecksumMismatch);
+    }
+
+    let entries = payload
+        .chunks(F::NUM_BYTES)
+        .map(|bytes| {
+            bincode::deserialize(bytes)
+                .map_err(|_| MatrixFileError::Malformed("Failed to deserialize entry."))
+        })
+        .collect::<Result<Vec<F>, _>>()?;
+
+    Ok(Array2::from_shape_vec((num_rows as usize, num_cols as usize), entries).unwrap())
+}
+
+/// Saves a matrix to a file in the binary container format.
+pub fn save_matrix<F: Field>(
+    path: &Path,
+    matrix: ArrayView2<F>,
+    kind: MatrixKind,
+) -> Result<(), MatrixFileError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_matrix(&mut writer, matrix, kind)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Loads a matrix saved using [`save_matrix`].
+pub fn load_matrix<F: Field>(path: &Path, kind: MatrixKind) -> Result<Array2<F>, MatrixFileError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    read_matrix(&mut reader, kind)
+}
@@ -1,116 +1,211 @@
-use ndarray::{Array, Array2, ArrayView1};
+use ndarray::{Array, Array2, ArrayView1, ArrayView2, ArrayViewMut1, ArrayViewMut2, Axis};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
 use std::hash::Hash;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
 
+pub mod barycentric;
+pub mod benes;
+pub mod binary;
+pub mod extract;
+mod field;
 pub mod galois;
-use galois::GF;
+pub mod linalg;
+pub mod matrix_file;
+pub mod poly;
+pub mod prime;
+pub mod ring;
+pub mod rmfe;
+pub mod rs;
+
+use barycentric::Barycentric;
+use benes::BenesNetwork;
+pub use field::Field;
+use matrix_file::MatrixFileError;
 
 /// Compute lagrange coefficients for interpolating a polynomial defined by evaluations at `cpos`
 /// to evaluations at `npos`.
 /// Returns a matrix with npos.len() rows and cpos.len() columns.
-pub fn lagrange_coeffs<const W: u8>(cpos: &[GF<W>], npos: &[GF<W>]) -> Array2<GF<W>> {
-    #[cfg(debug_assertions)]
-    {
-        let num_unique = |v: &[GF<W>]| {
-            let mut v: Vec<u32> = v.iter().map(|&x| x.into()).collect();
-            v.sort_unstable();
-            v.dedup();
-            v.len()
-        };
-
-        // cpos and npos should not have any repetition.
-        assert_eq!(num_unique(cpos), cpos.len());
-        assert_eq!(num_unique(npos), npos.len());
-    }
-
-    // Pre-compute denominators for all lagrange co-efficients.
-    // denom[i] = \prod_{j \neq i} (cpos[i] - cpos[j]).
-    let mut denom = Vec::with_capacity(cpos.len());
-    cpos.par_iter()
-        .map(|v| {
-            cpos.par_iter()
-                .map(|x| if v == x { GF::ONE } else { v - x })
-                .reduce(|| GF::ONE, |acc, x| acc * x)
-        })
-        .collect_into_vec(&mut denom);
-
-    // Compute lagrange coefficients.
-    let coeffs = npos
-        .par_iter()
-        .flat_map(|v| match cpos.par_iter().position_any(|x| x == v) {
-            Some(i) => {
-                // If v is a common value between npos and cpos then the lagrange coefficients
-                // simplify to selection.
-                let mut row = vec![GF::ZERO; cpos.len()];
-                row[i] = GF::ONE;
-                row
-            }
-            None => {
-                // Pre-compute the numerator = \prod (v - cpos[i]).
-                let numerator: GF<W> = cpos
-                    .par_iter()
-                    .map(|x| v - x)
-                    .reduce(|| GF::ONE, |acc, x| acc * x);
-
-                // The lagrange coefficient can now be computed using the pre-computed numerator
-                // and denominator.
-                cpos.par_iter()
-                    .zip_eq(denom.par_iter())
-                    .map(|(x, d)| numerator / ((v - x) * d))
-                    .collect()
-            }
-        })
-        .collect();
+pub fn lagrange_coeffs<F: Field>(cpos: &[F], npos: &[F]) -> Array2<F> {
+    // npos should not have any repetition, which is checked for cpos when computing weights.
+    debug_assert_eq!(
+        npos.iter().collect::<std::collections::HashSet<_>>().len(),
+        npos.len()
+    );
 
-    Array2::from_shape_vec((npos.len(), cpos.len()), coeffs).unwrap()
+    Barycentric::new(cpos).coeff_matrix(npos)
 }
 
 /// Outputs a super-invertible matrix with num_out rows and num_inp columns.
-pub fn super_inv_matrix<const W: u8>(num_inp: usize, num_out: usize) -> Array2<GF<W>> {
+pub fn super_inv_matrix<F: Field>(num_inp: usize, num_out: usize) -> Array2<F> {
     debug_assert!(num_inp >= num_out);
     rs_gen_mat(num_out, num_inp).reversed_axes()
 }
 
+/// Outputs a hyper-invertible matrix with num_out rows and num_inp columns.
+///
+/// The matrix maps evaluations of a polynomial of degree less than num_inp at the first num_inp
+/// positions to its evaluations at the next num_out positions, so every square submatrix is
+/// invertible. Fails if the field doesn't have num_inp + num_out distinct positions.
+pub fn hyper_inv_matrix<F: Field>(
+    num_inp: usize,
+    num_out: usize,
+) -> Result<Array2<F>, &'static str> {
+    let points = (0..(num_inp + num_out))
+        .map(|i| u32::try_from(i).map(F::from))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "Too many positions for the field.")?;
+
+    // Conversions from u32 wrap around once the positions run out, e.g., beyond the order of a
+    // binary field or the exceptional set of a Galois ring.
+    if points
+        .iter()
+        .collect::<std::collections::HashSet<_>>()
+        .len()
+        != points.len()
+    {
+        return Err("Too many positions for the field.");
+    }
+
+    Ok(lagrange_coeffs(&points[..num_inp], &points[num_inp..]))
+}
+
 /// Reads and constructs a binary super invertible matrix from a file.
-pub fn binary_super_inv_matrix<const W: u8>(path: &Path) -> Array2<GF<W>> {
-    let file = File::open(path).expect(
-        "Binary super-invertible matrix should be created using scripts/gen_binary_supmat.py.",
-    );
-    let reader = BufReader::new(file);
+///
+/// The file should have been created using `gen_binary_supmat` and contains the transpose of the
+/// matrix as lines of space-separated zeros and ones. Blank lines are ignored.
+pub fn binary_super_inv_matrix<F: Field>(path: &Path) -> Result<Array2<F>, MatrixFileError> {
+    let reader = BufReader::new(File::open(path)?);
 
     let mut matrix = Vec::new();
     let mut num_rows = 0;
-    for line in reader.lines() {
-        let line = line.unwrap();
+    let mut num_cols = None;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
 
-        for val in line.split(' ') {
+        let len = matrix.len();
+        for val in line.split_whitespace() {
             match val {
-                "0" => matrix.push(GF::ZERO),
-                "1" => matrix.push(GF::ONE),
-                _ => panic!("Binary super invertible matrix should only have binary entries"),
+                "0" => matrix.push(F::ZERO),
+                "1" => matrix.push(F::ONE),
+                _ => {
+                    return Err(MatrixFileError::InvalidEntry {
+                        line: i + 1,
+                        entry: val.to_string(),
+                    })
+                }
             }
         }
 
+        let found = matrix.len() - len;
+        match num_cols {
+            None => num_cols = Some(found),
+            Some(expected) if expected != found => {
+                return Err(MatrixFileError::RaggedRow {
+                    line: i + 1,
+                    expected,
+                    found,
+                })
+            }
+            Some(_) => (),
+        }
+
         num_rows += 1;
     }
 
-    let num_cols = matrix.len() / num_rows;
-    Array::from_shape_vec((num_rows, num_cols), matrix)
+    if matrix.is_empty() {
+        return Err(MatrixFileError::Empty);
+    }
+
+    let num_cols = num_cols.unwrap();
+    Ok(Array::from_shape_vec((num_rows, num_cols), matrix)
         .unwrap()
-        .reversed_axes()
+        .reversed_axes())
+}
+
+/// Number of random matrices tried by `gen_binary_super_inv_matrix` before giving up.
+const MAX_BINARY_ATTEMPTS: usize = 64;
+
+/// Generates a binary matrix with num_out rows and num_inp columns such that any num_honest
+/// columns have full rank, as checked by `linalg::check_super_invertible_for` using `mode`.
+///
+/// Matrices are sampled from a ChaCha12 RNG seeded with `seed`, so the output is reproducible.
+/// Over binary fields the minors of binary matrices lie in GF(2), so num_honest needs some slack
+/// over num_out for such matrices to exist.
+pub fn gen_binary_super_inv_matrix<F: Field>(
+    num_inp: usize,
+    num_out: usize,
+    num_honest: usize,
+    seed: u64,
+    mode: linalg::MinorCheck,
+) -> Result<Array2<F>, &'static str> {
+    if num_out > num_honest || num_honest > num_inp {
+        return Err("Number of honest inputs should lie between the number of outputs and inputs.");
+    }
+
+    let mut rng = ChaCha12Rng::seed_from_u64(seed);
+    for _ in 0..MAX_BINARY_ATTEMPTS {
+        let matrix = Array2::from_shape_simple_fn((num_out, num_inp), || {
+            if rng.gen() {
+                F::ONE
+            } else {
+                F::ZERO
+            }
+        });
+
+        if linalg::check_super_invertible_for(matrix.view(), num_honest, mode).is_ok() {
+            return Ok(matrix);
+        }
+    }
+
+    Err("Could not find a binary super-invertible matrix with the given dimensions.")
+}
+
+/// Writes a binary matrix in the format read by `binary_super_inv_matrix`.
+pub fn write_binary_super_inv_matrix<F: Field>(
+    matrix: ArrayView2<F>,
+    path: &Path,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    // The file stores the transpose since the reader reverses the axes.
+    for col in matrix.columns() {
+        let line = col
+            .iter()
+            .map(|&v| {
+                if v == F::ZERO {
+                    Ok("0")
+                } else if v == F::ONE {
+                    Ok("1")
+                } else {
+                    Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "Matrix should only have binary entries.",
+                    ))
+                }
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        writeln!(writer, "{}", line.join(" "))?;
+    }
+
+    writer.flush()
 }
 
 /// Outputs a reed-solomon generator with code_len rows and mssg_len columns.
-pub fn rs_gen_mat<const W: u8>(mssg_len: usize, code_len: usize) -> Array2<GF<W>> {
-    let mut matrix = Array::from_elem((code_len, 0), GF::ZERO);
+pub fn rs_gen_mat<F: Field>(mssg_len: usize, code_len: usize) -> Array2<F> {
+    let mut matrix = Array::from_elem((code_len, 0), F::ZERO);
 
     let col = Array::from_vec(
         (1u32..(code_len + 1).try_into().unwrap())
-            .map(GF::from)
+            .map(F::from)
             .collect(),
     );
     matrix.push_column(col.view()).unwrap();
@@ -124,7 +219,7 @@ pub fn rs_gen_mat<const W: u8>(mssg_len: usize, code_len: usize) -> Array2<GF<W>
 }
 
 /// Ordered selections with repititions.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Combination(Vec<usize>);
 
 impl Combination {
@@ -153,8 +248,139 @@ impl Combination {
         self.0.len()
     }
 
+    /// Returns true if the output is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The underlying map.
+    pub fn map(&self) -> &[usize] {
+        &self.0
+    }
+
     /// Apply the combination on a given input.
     pub fn apply<T: Copy>(&self, v: ArrayView1<T>) -> Vec<T> {
         self.0.iter().map(|&i| v[i]).collect()
     }
+
+    /// Apply the combination on a given input, writing the result into `out`.
+    pub fn apply_into<T: Copy>(&self, v: ArrayView1<T>, mut out: ArrayViewMut1<T>) {
+        assert_eq!(out.len(), self.len(), "Dimension mismatch.");
+
+        // Indexing a slice avoids multiplying every index by the stride of the view.
+        if let (Some(v), Some(out)) = (v.as_slice(), out.as_slice_mut()) {
+            for (o, &i) in out.iter_mut().zip(self.0.iter()) {
+                *o = v[i];
+            }
+            return;
+        }
+
+        for (o, &i) in out.iter_mut().zip(self.0.iter()) {
+            *o = v[i];
+        }
+    }
+
+    /// Apply the combination on every row of `inp` in parallel, writing the results into the
+    /// corresponding rows of `out`.
+    pub fn apply_batch_into<T: Copy + Send + Sync>(
+        &self,
+        inp: ArrayView2<T>,
+        mut out: ArrayViewMut2<T>,
+    ) {
+        assert_eq!(inp.nrows(), out.nrows(), "Dimension mismatch.");
+        assert_eq!(out.ncols(), self.len(), "Dimension mismatch.");
+
+        out.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .zip(inp.axis_iter(Axis(0)))
+            .for_each(|(out, v)| self.apply_into(v, out));
+    }
+
+    /// Apply the combination on every input in parallel, writing the result for `inps[i]` into
+    /// row i of `out`.
+    pub fn apply_many_into<T: Copy + Send + Sync>(
+        &self,
+        inps: &[ArrayView1<T>],
+        mut out: ArrayViewMut2<T>,
+    ) {
+        assert_eq!(inps.len(), out.nrows(), "Dimension mismatch.");
+        assert_eq!(out.ncols(), self.len(), "Dimension mismatch.");
+
+        out.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .zip(inps.par_iter())
+            .for_each(|(out, v)| self.apply_into(v.view(), out));
+    }
+
+    /// Selects rows of a matrix i.e., row i of the output is row map[i] of the input.
+    pub fn apply_rows<T: Clone>(&self, m: ArrayView2<T>) -> Array2<T> {
+        m.select(Axis(0), &self.0)
+    }
+
+    /// Selects columns of a matrix i.e., column i of the output is column map[i] of the input.
+    pub fn apply_cols<T: Clone>(&self, m: ArrayView2<T>) -> Array2<T> {
+        m.select(Axis(1), &self.0)
+    }
+
+    /// Combination equivalent to applying `first` and then `self`.
+    ///
+    /// The maximum value in the map of `self` should be less than the length of `first`.
+    pub fn compose(&self, first: &Self) -> Self {
+        Self(self.0.iter().map(|&i| first.0[i]).collect())
+    }
+
+    /// Returns true if the map is a permutation of 0, ..., len - 1.
+    pub fn is_permutation(&self) -> bool {
+        let mut seen = vec![false; self.len()];
+        self.0
+            .iter()
+            .all(|&i| i < seen.len() && !std::mem::replace(&mut seen[i], true))
+    }
+
+    /// Inverse of a permutation, or `None` if the map is not a permutation.
+    pub fn inverse(&self) -> Option<Self> {
+        if !self.is_permutation() {
+            return None;
+        }
+
+        let mut inv = vec![0; self.len()];
+        for (i, &v) in self.0.iter().enumerate() {
+            inv[v] = i;
+        }
+        Some(Self(inv))
+    }
+
+    /// Cycles of a permutation, or `None` if the map is not a permutation.
+    ///
+    /// Each cycle (c_0, ..., c_{k-1}) satisfies map[c_j] = c_{j+1 mod k}. Cycles are ordered by
+    /// their smallest element, which they start with, and fixed points are included.
+    pub fn cycles(&self) -> Option<Vec<Vec<usize>>> {
+        if !self.is_permutation() {
+            return None;
+        }
+
+        let mut seen = vec![false; self.len()];
+        let mut cycles = Vec::new();
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+
+            let mut cycle = Vec::new();
+            let mut cur = start;
+            while !seen[cur] {
+                seen[cur] = true;
+                cycle.push(cur);
+                cur = self.0[cur];
+            }
+            cycles.push(cycle);
+        }
+
+        Some(cycles)
+    }
+
+    /// Beneš network routing a permutation, or `None` if the map is not a permutation.
+    pub fn benes(&self) -> Option<BenesNetwork> {
+        self.is_permutation().then(|| BenesNetwork::new(&self.0))
+    }
 }
@@ -0,0 +1,249 @@
+//! Univariate polynomials over a field.
+use super::Field;
+use rayon::prelude::*;
+use std::ops::{Add, Mul, Sub};
+
+/// Polynomial with coefficients in `F`, stored in increasing order of degree.
+///
+/// The leading coefficient is always non-zero, so the zero polynomial has no coefficients.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Poly<F: Field> {
+    coeffs: Vec<F>,
+}
+
+impl<F: Field> Poly<F> {
+    /// Polynomial with the given coefficients in increasing order of degree.
+    pub fn new(mut coeffs: Vec<F>) -> Self {
+        while coeffs.last().is_some_and(|c| c.is_zero()) {
+            coeffs.pop();
+        }
+        Self { coeffs }
+    }
+
+    /// The zero polynomial.
+    pub fn zero() -> Self {
+        Self { coeffs: Vec::new() }
+    }
+
+    /// Constant polynomial.
+    pub fn constant(c: F) -> Self {
+        Self::new(vec![c])
+    }
+
+    /// The polynomial x - `root`.
+    pub fn linear(root: F) -> Self {
+        Self::new(vec![F::ZERO - root, F::ONE])
+    }
+
+    /// Coefficients in increasing order of degree.
+    pub fn coeffs(&self) -> &[F] {
+        &self.coeffs
+    }
+
+    /// Degree of the polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Returns true for the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Coefficient of the highest degree term, or zero for the zero polynomial.
+    pub fn leading_coeff(&self) -> F {
+        self.coeffs.last().cloned().unwrap_or(F::ZERO)
+    }
+
+    /// Evaluate at `x` using Horner's rule.
+    pub fn eval(&self, x: F) -> F {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(F::ZERO, |acc, &c| acc * x + c)
+    }
+
+    /// Evaluate at every point in `xs`.
+    ///
+    /// Points are evaluated in parallel.
+    pub fn eval_many(&self, xs: &[F]) -> Vec<F> {
+        xs.par_iter().map(|&x| self.eval(x)).collect()
+    }
+
+    /// The unique polynomial of degree less than `xs.len()` that evaluates to `ys[i]` at `xs[i]`.
+    ///
+    /// The differences between the points should be invertible.
+    pub fn interpolate(xs: &[F], ys: &[F]) -> Self {
+        debug_assert_eq!(xs.len(), ys.len());
+
+        // Vanishing polynomial of all the points.
+        let vanishing = xs
+            .iter()
+            .fold(Self::constant(F::ONE), |acc, &x| &acc * &Self::linear(x));
+
+        // Sum of ys[i] * L_i where L_i = vanishing / (x - xs[i]) scaled to be one at xs[i].
+        xs.par_iter()
+            .zip_eq(ys.par_iter())
+            .map(|(&x, &y)| {
+                let (basis, _) = vanishing.div_rem(&Self::linear(x));
+                basis.scale(y / basis.eval(x))
+            })
+            .reduce(Self::zero, |acc, p| &acc + &p)
+    }
+
+    /// Multiply every coefficient by `c`.
+    pub fn scale(&self, c: F) -> Self {
+        Self::new(self.coeffs.iter().map(|&v| v * c).collect())
+    }
+
+    /// Scale so that the leading coefficient is one. The zero polynomial is left unchanged.
+    pub fn monic(&self) -> Self {
+        match self.leading_coeff().inv() {
+            Some(inv) => self.scale(inv),
+            None => self.clone(),
+        }
+    }
+
+    /// Quotient and remainder of division by `divisor`.
+    ///
+    /// # Panics
+    /// Panics if the leading coefficient of `divisor` is not invertible, e.g., if it is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        self.checked_div_rem(divisor)
+            .expect("division by a polynomial with non-invertible leading coefficient")
+    }
+
+    /// Quotient and remainder of division by `divisor`, or `None` if its leading coefficient is
+    /// not invertible. Over a field this only happens for the zero polynomial, while over a
+    /// Galois ring the leading coefficient can also be a non-zero non-unit.
+    pub fn checked_div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        let lead_inv = divisor.leading_coeff().inv()?;
+
+        let dlen = divisor.coeffs.len();
+        if self.coeffs.len() < dlen {
+            return Some((Self::zero(), self.clone()));
+        }
+
+        let mut rem = self.coeffs.clone();
+        let mut quot = vec![F::ZERO; rem.len() - dlen + 1];
+        for shift in (0..quot.len()).rev() {
+            let c = rem[shift + dlen - 1] * lead_inv;
+            quot[shift] = c;
+            for (r, &d) in rem[shift..].iter_mut().zip(divisor.coeffs.iter()) {
+                *r -= c * d;
+            }
+        }
+
+        rem.truncate(dlen - 1);
+        Some((Self::new(quot), Self::new(rem)))
+    }
+
+    /// Monic greatest common divisor.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, rem) = a.div_rem(&b);
+            a = b;
+            b = rem;
+        }
+        a.monic()
+    }
+
+    /// Formal derivative.
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| mul_int(c, i))
+                .collect(),
+        )
+    }
+}
+
+/// Computes `c` added to itself `n` times.
+///
+/// `F::from` is not used since it doesn't map integers to multiples of one in binary fields.
+fn mul_int<F: Field>(c: F, mut n: usize) -> F {
+    let (mut base, mut res) = (c, F::ZERO);
+    while n > 0 {
+        if n & 1 == 1 {
+            res += base;
+        }
+        base += base;
+        n >>= 1;
+    }
+    res
+}
+
+impl<F: Field> Add for &Poly<F> {
+    type Output = Poly<F>;
+
+    fn add(self, other: Self) -> Poly<F> {
+        let (long, short) = if self.coeffs.len() >= other.coeffs.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let mut coeffs = long.coeffs.clone();
+        for (c, &v) in coeffs.iter_mut().zip(short.coeffs.iter()) {
+            *c += v;
+        }
+        Poly::new(coeffs)
+    }
+}
+
+impl<F: Field> Sub for &Poly<F> {
+    type Output = Poly<F>;
+
+    fn sub(self, other: Self) -> Poly<F> {
+        let len = self.coeffs.len().max(other.coeffs.len());
+        let coeff = |p: &Poly<F>, i: usize| p.coeffs.get(i).cloned().unwrap_or(F::ZERO);
+
+        Poly::new((0..len).map(|i| coeff(self, i) - coeff(other, i)).collect())
+    }
+}
+
+impl<F: Field> Mul for &Poly<F> {
+    type Output = Poly<F>;
+
+    fn mul(self, other: Self) -> Poly<F> {
+        if self.is_zero() || other.is_zero() {
+            return Poly::zero();
+        }
+
+        let mut coeffs = vec![F::ZERO; self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, &x) in self.coeffs.iter().enumerate() {
+            for (j, &y) in other.coeffs.iter().enumerate() {
+                coeffs[i + j] += x * y;
+            }
+        }
+        Poly::new(coeffs)
+    }
+}
+
+impl<F: Field> Add for Poly<F> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        &self + &other
+    }
+}
+
+impl<F: Field> Sub for Poly<F> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        &self - &other
+    }
+}
+
+impl<F: Field> Mul for Poly<F> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        &self * &other
+    }
+}
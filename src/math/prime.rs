@@ -0,0 +1,222 @@
+//! Prime fields.
+use super::Field;
+use num_traits::identities::{One, Zero};
+use rand::distributions::{Distribution, Uniform};
+use rand::Rng;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::Serialize;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// Elements of the prime field with modulus equal to the Mersenne prime 2^61 - 1.
+///
+/// Elements are always stored in reduced form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(into = "u64")]
+pub struct Mersenne61(u64);
+
+impl Mersenne61 {
+    /// Modulus of the field.
+    pub const MODULUS: u64 = (1 << 61) - 1;
+
+    /// Number of bytes when serialized.
+    pub const NUM_BYTES: usize = 8;
+
+    /// Additive identity.
+    pub const ZERO: Self = Self(0);
+
+    /// Multiplicative identity.
+    pub const ONE: Self = Self(1);
+
+    /// Sample an element uniformly at random from the field.
+    pub fn rand<R: Rng>(rng: &mut R) -> Self {
+        Self(Uniform::new(0, Self::MODULUS).sample(rng))
+    }
+
+    /// Multiplicative inverse, or `None` for zero.
+    pub fn inv(&self) -> Option<Self> {
+        if self.0 == 0 {
+            None
+        } else {
+            // Fermat's little theorem.
+            Some(self.pow(Self::MODULUS - 2))
+        }
+    }
+
+    /// Raise to the power `exp`.
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut base = *self;
+        let mut res = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// Reduce a value less than 2^122 modulo the field modulus.
+    #[inline]
+    fn reduce(x: u128) -> u64 {
+        let lo = (x as u64) & Self::MODULUS;
+        let hi = (x >> 61) as u64;
+        Self::reduce_once(lo + hi)
+    }
+
+    /// Reduce a value less than twice the modulus.
+    #[inline]
+    fn reduce_once(x: u64) -> u64 {
+        if x >= Self::MODULUS {
+            x - Self::MODULUS
+        } else {
+            x
+        }
+    }
+}
+
+impl From<u32> for Mersenne61 {
+    fn from(value: u32) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<u64> for Mersenne61 {
+    fn from(value: u64) -> Self {
+        Self(Self::reduce(value.into()))
+    }
+}
+
+impl From<Mersenne61> for u64 {
+    fn from(value: Mersenne61) -> u64 {
+        value.0
+    }
+}
+
+impl<'de> Deserialize<'de> for Mersenne61 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Unlike `From<u64>`, don't reduce so that every element has a unique encoding.
+        let val = u64::deserialize(deserializer)?;
+        if val >= Self::MODULUS {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(val),
+                &"an integer less than 2^61 - 1",
+            ));
+        }
+        Ok(Self(val))
+    }
+}
+
+impl MulAssign for Mersenne61 {
+    fn mul_assign(&mut self, rhs: Self) {
+        self.0 = Self::reduce(u128::from(self.0) * u128::from(rhs.0));
+    }
+}
+
+impl Mul for Mersenne61 {
+    type Output = Self;
+
+    fn mul(mut self, other: Self) -> Self {
+        self *= other;
+        self
+    }
+}
+
+impl AddAssign for Mersenne61 {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 = Self::reduce_once(self.0 + rhs.0);
+    }
+}
+
+impl Add for Mersenne61 {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl SubAssign for Mersenne61 {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 = Self::reduce_once(self.0 + Self::MODULUS - rhs.0);
+    }
+}
+
+impl Sub for Mersenne61 {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl DivAssign for Mersenne61 {
+    fn div_assign(&mut self, rhs: Self) {
+        *self *= rhs.inv().expect("division by zero");
+    }
+}
+
+impl Div for Mersenne61 {
+    type Output = Self;
+
+    fn div(mut self, rhs: Self) -> Self {
+        self /= rhs;
+        self
+    }
+}
+
+impl Zero for Mersenne61 {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl One for Mersenne61 {
+    fn one() -> Self {
+        Self::ONE
+    }
+}
+
+impl Field for Mersenne61 {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const NUM_BYTES: usize = Self::NUM_BYTES;
+    const WIDTH: usize = 61;
+    const CHARACTERISTIC: u128 = Self::MODULUS as u128;
+    const DEGREE: usize = 1;
+
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        Self::rand(rng)
+    }
+
+    fn inv(&self) -> Option<Self> {
+        self.inv()
+    }
+
+    fn dot(a: &[Self], b: &[Self]) -> Self {
+        debug_assert_eq!(a.len(), b.len());
+
+        // Each product is less than 2^122, so 32 of them can be summed before reducing.
+        let mut res = Self::ZERO;
+        for (a, b) in a.chunks(32).zip(b.chunks(32)) {
+            let acc: u128 = a
+                .iter()
+                .zip(b.iter())
+                .map(|(x, y)| u128::from(x.0) * u128::from(y.0))
+                .sum();
+            let acc = (acc & u128::from(Self::MODULUS)) + (acc >> 61);
+            res += Self(Self::reduce(acc));
+        }
+        res
+    }
+}
@@ -0,0 +1,323 @@
+//! Galois rings GR(2^K, D) = (Z/2^K)[x] / (h(x)).
+//!
+//! h is the primitive polynomial of degree D used for `GF<D>`, so reducing coefficients modulo 2
+//! maps the ring onto GF(2^D). An element is a unit if and only if its image in GF(2^D) is
+//! non-zero.
+//!
+//! The integers Z/2^K are the constant polynomials, but only the differences between 0 and 1 are
+//! units among them. Interpolation instead uses the exceptional set of polynomials with
+//! coefficients in {0, 1}, which has size 2^D and is what `From<u32>` maps into.
+use super::galois::native::{MAX_WIDTH, PRIM_POLY};
+use super::Field;
+use num_traits::identities::{One, Zero};
+use rand::Rng;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// Elements of the Galois ring GR(2^K, D).
+///
+/// K should be between 1 and 64 and D between 1 and 30, which is checked at compile time when
+/// multiplying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GR<const K: u8, const D: usize>([u64; D]);
+
+impl<const K: u8, const D: usize> GR<K, D> {
+    /// Mask for reducing coefficients modulo 2^K.
+    const MASK: u64 = u64::MAX >> (64 - K as u32);
+
+    /// Number of bytes used to serialize a coefficient.
+    const COEFF_BYTES: usize = (K as usize).div_ceil(8);
+
+    /// Number of bytes when serialized.
+    pub const NUM_BYTES: usize = D * Self::COEFF_BYTES;
+
+    /// Size of the exceptional set i.e., the number of valid positions for interpolation.
+    pub const EXCEPTIONAL_SET_SIZE: u64 = 1 << D;
+
+    /// Additive identity.
+    pub const ZERO: Self = Self([0; D]);
+
+    /// Multiplicative identity.
+    pub const ONE: Self = {
+        let mut coeffs = [0; D];
+        coeffs[0] = 1;
+        Self(coeffs)
+    };
+
+    /// Embed an integer modulo 2^K as a constant polynomial.
+    pub fn from_int(value: u64) -> Self {
+        let mut coeffs = [0; D];
+        coeffs[0] = value & Self::MASK;
+        Self(coeffs)
+    }
+
+    /// Constant coefficient i.e., the inverse of [`GR::from_int`] on constant polynomials.
+    pub fn to_int(&self) -> u64 {
+        self.0[0]
+    }
+
+    /// Coefficients in increasing order of degree.
+    pub fn coeffs(&self) -> &[u64; D] {
+        &self.0
+    }
+
+    /// Sample an element uniformly at random from the ring.
+    pub fn rand<R: Rng>(rng: &mut R) -> Self {
+        Self(std::array::from_fn(|_| rng.gen::<u64>() & Self::MASK))
+    }
+
+    /// Returns true if the element has a multiplicative inverse.
+    pub fn is_unit(&self) -> bool {
+        self.0.iter().any(|&c| c & 1 == 1)
+    }
+
+    /// Multiplicative inverse, or `None` for non-units.
+    pub fn inv(&self) -> Option<Self> {
+        if !self.is_unit() {
+            return None;
+        }
+
+        // x^(2^D - 2) is an inverse modulo 2, which Newton iteration v <- v * (2 - x * v) lifts
+        // to an inverse modulo 2^K by doubling the precision in every step.
+        let mut v = self.pow((1 << D) - 2);
+        let two = Self::from_int(2);
+        let mut prec = 1;
+        while prec < K {
+            v *= two - *self * v;
+            prec *= 2;
+        }
+        Some(v)
+    }
+
+    /// Raise to the power `exp`.
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut base = *self;
+        let mut res = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// Fails to compile if arithmetic is used with unsupported parameters.
+    #[inline(always)]
+    fn assert_supported() {
+        const {
+            assert!(
+                K >= 1 && K <= 64 && D >= 1 && D <= MAX_WIDTH as usize,
+                "GR<K, D> only supports K from 1 to 64 and D from 1 to 30"
+            )
+        };
+    }
+
+    fn mul_raw(x: &[u64; D], y: &[u64; D]) -> [u64; D] {
+        Self::assert_supported();
+
+        // The product has degree at most 2D - 2, and D is bounded so it fits on the stack.
+        let mut buf = [0u64; 2 * MAX_WIDTH as usize - 1];
+        let prod = &mut buf[..(2 * D - 1)];
+        for (i, &a) in x.iter().enumerate() {
+            for (j, &b) in y.iter().enumerate() {
+                prod[i + j] = prod[i + j].wrapping_add(a.wrapping_mul(b));
+            }
+        }
+
+        // Reduce using x^D = -(h(x) - x^D), from the highest degree downwards.
+        let poly = PRIM_POLY[D];
+        for i in (D..(2 * D - 1)).rev() {
+            let c = prod[i];
+            for t in (0..D).filter(|t| (poly >> t) & 1 == 1) {
+                prod[i - D + t] = prod[i - D + t].wrapping_sub(c);
+            }
+        }
+
+        std::array::from_fn(|i| prod[i] & Self::MASK)
+    }
+}
+
+impl<const K: u8, const D: usize> From<u32> for GR<K, D> {
+    /// Maps `value` to the element of the exceptional set whose coefficients are the bits of
+    /// `value`. Bits beyond the D-th are ignored, so only values below 2^D map to distinct
+    /// elements.
+    fn from(value: u32) -> Self {
+        Self(std::array::from_fn(|i| {
+            if i < 32 {
+                u64::from((value >> i) & 1)
+            } else {
+                0
+            }
+        }))
+    }
+}
+
+impl<const K: u8, const D: usize> MulAssign for GR<K, D> {
+    fn mul_assign(&mut self, rhs: Self) {
+        self.0 = Self::mul_raw(&self.0, &rhs.0);
+    }
+}
+
+impl<const K: u8, const D: usize> Mul for GR<K, D> {
+    type Output = Self;
+
+    fn mul(mut self, other: Self) -> Self {
+        self *= other;
+        self
+    }
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const K: u8, const D: usize> AddAssign for GR<K, D> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (x, y) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *x = x.wrapping_add(*y) & Self::MASK;
+        }
+    }
+}
+
+impl<const K: u8, const D: usize> Add for GR<K, D> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const K: u8, const D: usize> SubAssign for GR<K, D> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (x, y) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *x = x.wrapping_sub(*y) & Self::MASK;
+        }
+    }
+}
+
+impl<const K: u8, const D: usize> Sub for GR<K, D> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const K: u8, const D: usize> DivAssign for GR<K, D> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self *= rhs.inv().expect("division by a non-unit");
+    }
+}
+
+impl<const K: u8, const D: usize> Div for GR<K, D> {
+    type Output = Self;
+
+    fn div(mut self, rhs: Self) -> Self {
+        self /= rhs;
+        self
+    }
+}
+
+impl<const K: u8, const D: usize> Zero for GR<K, D> {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|&c| c == 0)
+    }
+}
+
+impl<const K: u8, const D: usize> One for GR<K, D> {
+    fn one() -> Self {
+        Self::ONE
+    }
+}
+
+impl<const K: u8, const D: usize> Field for GR<K, D> {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const NUM_BYTES: usize = Self::NUM_BYTES;
+    const WIDTH: usize = K as usize * D;
+    const CHARACTERISTIC: u128 = 1 << K;
+    const DEGREE: usize = D;
+
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        Self::rand(rng)
+    }
+
+    fn inv(&self) -> Option<Self> {
+        self.inv()
+    }
+}
+
+impl<const K: u8, const D: usize> Serialize for GR<K, D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tup = serializer.serialize_tuple(Self::NUM_BYTES)?;
+        for c in self.0.iter() {
+            let val = c.to_le_bytes();
+            for v in val.iter().take(Self::COEFF_BYTES) {
+                tup.serialize_element(v)?;
+            }
+        }
+        tup.end()
+    }
+}
+
+impl<'de, const K: u8, const D: usize> Deserialize<'de> for GR<K, D> {
+    fn deserialize<D2>(deserializer: D2) -> Result<Self, D2::Error>
+    where
+        D2: Deserializer<'de>,
+    {
+        let visitor = GRVisitor;
+        deserializer.deserialize_tuple(GR::<K, D>::NUM_BYTES, visitor)
+    }
+}
+
+struct GRVisitor<const K: u8, const D: usize>;
+
+impl<'de, const K: u8, const D: usize> Visitor<'de> for GRVisitor<K, D> {
+    type Value = GR<K, D>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "a sequence of {} u8 integers encoding coefficients less than 2^{}",
+            GR::<K, D>::NUM_BYTES,
+            K
+        )
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut coeffs = [0u64; D];
+        for (i, c) in coeffs.iter_mut().enumerate() {
+            let mut vals = [0u8; 8];
+            for (j, v) in vals.iter_mut().take(GR::<K, D>::COEFF_BYTES).enumerate() {
+                *v = seq.next_element()?.ok_or_else(|| {
+                    de::Error::invalid_length(i * GR::<K, D>::COEFF_BYTES + j, &self)
+                })?;
+            }
+
+            // The serialized bytes can hold values outside Z/2^K if K is not a multiple of 8.
+            *c = u64::from_le_bytes(vals);
+            if *c > GR::<K, D>::MASK {
+                return Err(de::Error::invalid_value(
+                    de::Unexpected::Unsigned(*c),
+                    &self,
+                ));
+            }
+        }
+
+        Ok(GR(coeffs))
+    }
+}
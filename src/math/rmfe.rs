@@ -0,0 +1,221 @@
+//! Reverse multiplication-friendly embeddings (RMFE) of GF(2)^k into GF(2^W).
+//!
+//! An RMFE is a pair of GF(2)-linear maps phi: GF(2)^k -> GF(2^W) and psi: GF(2^W) -> GF(2)^k
+//! such that psi(phi(x) * phi(y)) = x * y, where the product on the right is component-wise.
+//! This allows k AND gates to be evaluated with one field multiplication.
+//!
+//! The construction interpolates the bits by a polynomial of degree less than k over a subfield
+//! K = GF(2^a), using k points of K, and evaluates it at a generator of GF(2^W) over K. The
+//! product of two such polynomials has degree at most 2k - 2, so it can be read back from its
+//! coordinates as long as 2k - 1 <= W / a. The maps also satisfy psi(phi(x)) = x.
+use super::galois::GF;
+use crate::sharing::{PackedShare, PackedSharing};
+use crate::ProtoErrorKind;
+use ndarray::ArrayView;
+use rand::Rng;
+
+/// RMFE mapping k bits into GF(2^W).
+#[derive(Clone, Debug)]
+pub struct Rmfe<const W: u8> {
+    // Images of the unit vectors under phi.
+    phi: Vec<GF<W>>,
+    // Rows of psi as a binary matrix, with one bit per bit of the field element.
+    psi: Vec<u32>,
+}
+
+impl<const W: u8> Rmfe<W> {
+    /// Constructs the RMFE with the largest number of bits per field element.
+    ///
+    /// For example, this packs 2 bits for W = 8, 3 bits for W = 12 and 4 bits for W = 16.
+    pub fn new() -> Self {
+        let (a, k) = (1..=W)
+            .filter(|&a| W.is_multiple_of(a))
+            .map(|a| (a, Self::max_bits(a)))
+            .max_by_key(|&(a, k)| (k, std::cmp::Reverse(a)))
+            .unwrap();
+
+        Self::with_params(a, k).unwrap()
+    }
+
+    /// Constructs an RMFE of `k` bits by interpolating over the subfield GF(2^a).
+    pub fn with_params(a: u8, k: usize) -> Result<Self, &'static str> {
+        if a == 0 || !W.is_multiple_of(a) {
+            return Err("Subfield width should divide the field width.");
+        }
+        if k == 0 || k > Self::max_bits(a) {
+            return Err("Too many bits for the given subfield.");
+        }
+
+        // Generator of the subfield and of the field over the subfield respectively.
+        let x = GF::<W>::generator();
+        let g = x.pow((GF::<W>::ORDER as u64 - 1) / ((1 << a) - 1));
+        let alpha = x;
+
+        // Interpolation points: 0 followed by powers of the generator of the subfield.
+        let points: Vec<_> = std::iter::once(GF::ZERO)
+            .chain(std::iter::successors(Some(GF::ONE), |&v| Some(v * g)))
+            .take(k)
+            .collect();
+
+        // phi maps the j-th unit vector to the j-th lagrange polynomial evaluated at alpha.
+        let phi: Vec<_> = (0..k)
+            .map(|j| {
+                points
+                    .iter()
+                    .enumerate()
+                    .filter(|&(m, _)| m != j)
+                    .fold(GF::ONE, |acc, (_, &p)| acc * (alpha - p) / (points[j] - p))
+            })
+            .collect();
+
+        // GF(2)-basis of the field given by beta_t * alpha^i for t < a and i < n, where the
+        // subfield basis beta_t = g^t starts with 1.
+        let beta: Vec<_> = std::iter::successors(Some(GF::<W>::ONE), |&v| Some(v * g))
+            .take(a as usize)
+            .collect();
+        let basis: Vec<_> = std::iter::successors(Some(GF::<W>::ONE), |&v| Some(v * alpha))
+            .take((W / a).into())
+            .flat_map(|ai| beta.iter().map(move |&b| u32::from(ai * b)))
+            .collect();
+        let coords = invert_basis(&basis);
+        let coord = |v: GF<W>| -> u32 {
+            (0..W)
+                .filter(|b| (u32::from(v) >> b) & 1 == 1)
+                .fold(0, |acc, b| acc ^ coords[b as usize])
+        };
+
+        // Projection from the subfield to GF(2) that fixes GF(2).
+        let proj = |v: GF<W>| coord(v) & 1;
+
+        // psi evaluates the polynomial given by the coordinates of its input at each point.
+        let mut psi = vec![0u32; k];
+        for b in 0..W {
+            let c = coords[b as usize];
+            let poly: Vec<GF<W>> = (0..(2 * k - 1))
+                .map(|i| {
+                    beta.iter()
+                        .enumerate()
+                        .filter(|&(t, _)| (c >> (i * a as usize + t)) & 1 == 1)
+                        .fold(GF::ZERO, |acc, (_, &v)| acc + v)
+                })
+                .collect();
+
+            for (row, &p) in psi.iter_mut().zip(points.iter()) {
+                let val = poly.iter().rev().fold(GF::ZERO, |acc, &v| acc * p + v);
+                *row |= proj(val) << b;
+            }
+        }
+
+        Ok(Self { phi, psi })
+    }
+
+    /// Maximum number of bits when interpolating over the subfield GF(2^a).
+    fn max_bits(a: u8) -> usize {
+        let n = (W / a) as usize;
+        (1usize << a).min(n.div_ceil(2))
+    }
+
+    /// Number of bits embedded in a field element.
+    pub fn num_bits(&self) -> usize {
+        self.phi.len()
+    }
+
+    /// Embed up to `num_bits` bits into a field element; missing bits are taken to be zero.
+    pub fn encode(&self, bits: &[bool]) -> GF<W> {
+        debug_assert!(bits.len() <= self.num_bits());
+
+        bits.iter()
+            .zip(self.phi.iter())
+            .filter(|&(&b, _)| b)
+            .fold(GF::ZERO, |acc, (_, &v)| acc + v)
+    }
+
+    /// Recover `num_bits` bits from a field element.
+    pub fn decode(&self, val: GF<W>) -> Vec<bool> {
+        let val = u32::from(val);
+        self.psi
+            .iter()
+            .map(|&row| (row & val).count_ones() % 2 == 1)
+            .collect()
+    }
+
+    /// Embed bits into field elements, `num_bits` at a time.
+    pub fn encode_all(&self, bits: &[bool]) -> Vec<GF<W>> {
+        bits.chunks(self.num_bits())
+            .map(|chunk| self.encode(chunk))
+            .collect()
+    }
+
+    /// Recover the first `len` bits embedded in a sequence of field elements.
+    pub fn decode_all(&self, vals: &[GF<W>], len: usize) -> Vec<bool> {
+        vals.iter()
+            .flat_map(|&v| self.decode(v))
+            .take(len)
+            .collect()
+    }
+
+    /// Packed secret share up to `num_bits * num_secrets` bits.
+    ///
+    /// Multiplying shares of two such sharings results in a sharing of double the degree whose
+    /// reconstruction decodes to the AND of the bits.
+    pub fn share<R: Rng>(
+        &self,
+        pss: &PackedSharing<GF<W>>,
+        bits: &[bool],
+        rng: &mut R,
+    ) -> Vec<PackedShare<GF<W>>> {
+        let l = pss.num_secrets() as usize;
+        debug_assert!(bits.len() <= l * self.num_bits());
+
+        let mut secrets = self.encode_all(bits);
+        secrets.resize(l, GF::ZERO);
+        pss.share(ArrayView::from(&secrets), rng)
+    }
+
+    /// Reconstruct the bits from a packed sharing created by [`Rmfe::share`] or a product of such
+    /// sharings.
+    pub fn recon(
+        &self,
+        pss: &PackedSharing<GF<W>>,
+        shares: &[PackedShare<GF<W>>],
+    ) -> Result<Vec<bool>, ProtoErrorKind> {
+        let secrets = pss.recon(ArrayView::from(shares))?;
+        Ok(secrets.into_iter().flat_map(|v| self.decode(v)).collect())
+    }
+}
+
+impl<const W: u8> Default for Rmfe<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Given a basis of GF(2)^w as bit vectors, returns the coordinates of each unit vector in that
+/// basis as bit masks.
+fn invert_basis(basis: &[u32]) -> Vec<u32> {
+    let w = basis.len();
+
+    // Pairs of (vector, coordinates) kept in reduced row echelon form.
+    let mut rows: Vec<(u32, u32)> = basis
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (v, 1 << i))
+        .collect();
+
+    for b in 0..w {
+        let pivot = (b..w)
+            .find(|&i| (rows[i].0 >> b) & 1 == 1)
+            .expect("Vectors should form a basis.");
+        rows.swap(b, pivot);
+
+        let (pv, pc) = rows[b];
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i != b && (row.0 >> b) & 1 == 1 {
+                row.0 ^= pv;
+                row.1 ^= pc;
+            }
+        }
+    }
+
+    rows.into_iter().map(|(_, c)| c).collect()
+}
@@ -0,0 +1,168 @@
+//! Reed-Solomon codes given by evaluations of polynomials of degree less than k.
+//!
+//! Codewords are encoded systematically i.e., the message is the evaluations at the first k
+//! points, so the remaining n - k evaluations are a linear function P of the message. The
+//! parity-check matrix is then [P | -I] and the syndrome of a word c is P c[..k] - c[k..], which
+//! is zero exactly for codewords.
+//!
+//! Decoding uses Gao's algorithm, which only works over fields since it runs the extended
+//! euclidean algorithm on polynomials.
+use super::poly::Poly;
+use super::{lagrange_coeffs, Field};
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2};
+
+/// Systematic Reed-Solomon code of length n and dimension k.
+#[derive(Debug, Clone)]
+pub struct RsCode<F: Field> {
+    points: Vec<F>,
+    k: usize,
+    // Maps the first k evaluations to the remaining n - k evaluations.
+    parity: Array2<F>,
+}
+
+impl<F: Field> RsCode<F> {
+    /// Code consisting of evaluations at `points` of polynomials of degree less than `k`.
+    pub fn new(points: &[F], k: usize) -> Self {
+        assert!(k <= points.len(), "Dimension should be at most the length.");
+
+        Self {
+            points: points.to_vec(),
+            k,
+            parity: lagrange_coeffs(&points[..k], &points[k..]),
+        }
+    }
+
+    /// Evaluation points.
+    pub fn points(&self) -> &[F] {
+        &self.points
+    }
+
+    /// Length of codewords.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if codewords are empty.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Length of messages.
+    pub fn dim(&self) -> usize {
+        self.k
+    }
+
+    /// Systematic generator with n rows and k columns, whose first k rows form the identity.
+    pub fn gen_mat(&self) -> Array2<F> {
+        let mut res = Array2::from_elem((self.len(), self.k), F::ZERO);
+        for i in 0..self.k {
+            res[[i, i]] = F::ONE;
+        }
+        res.slice_mut(s![self.k.., ..]).assign(&self.parity);
+        res
+    }
+
+    /// Parity-check matrix with n - k rows and n columns, whose kernel is the code.
+    pub fn parity_check_mat(&self) -> Array2<F> {
+        let (n, k) = (self.len(), self.k);
+        let mut res = Array2::from_elem((n - k, n), F::ZERO);
+        res.slice_mut(s![.., ..k]).assign(&self.parity);
+        for i in 0..(n - k) {
+            res[[i, k + i]] = F::ZERO - F::ONE;
+        }
+        res
+    }
+
+    /// Codeword whose first k entries are `msg`.
+    pub fn encode(&self, msg: ArrayView1<F>) -> Array1<F> {
+        debug_assert_eq!(msg.len(), self.k);
+
+        let mut res = msg.to_vec();
+        res.extend(F::mat_vec(self.parity.view(), msg));
+        Array1::from_vec(res)
+    }
+
+    /// Syndrome of a word of length n, which is zero if and only if it is a codeword.
+    pub fn syndrome(&self, word: ArrayView1<F>) -> Array1<F> {
+        assert_eq!(word.len(), self.len(), "Dimension mismatch.");
+
+        let mut res = F::mat_vec(self.parity.view(), word.slice(s![..self.k]));
+        res -= &word.slice(s![self.k..]);
+        res
+    }
+
+    /// Syndromes of a batch of words given as the columns of `words`, with one column per word
+    /// in the output.
+    pub fn syndromes(&self, words: ArrayView2<F>) -> Array2<F> {
+        assert_eq!(words.nrows(), self.len(), "Dimension mismatch.");
+
+        let mut res = F::mat_mul(self.parity.view(), words.slice(s![..self.k, ..]));
+        res -= &words.slice(s![self.k.., ..]);
+        res
+    }
+
+    /// Returns true if `word` is a codeword.
+    pub fn is_codeword(&self, word: ArrayView1<F>) -> bool {
+        self.syndrome(word).iter().all(|v| v.is_zero())
+    }
+}
+
+/// Maximum number of errors that can be corrected in codewords of length n with messages of
+/// length k.
+pub fn max_errors(n: usize, k: usize) -> usize {
+    n.saturating_sub(k) / 2
+}
+
+/// Decodes the evaluations `ys` at distinct points `xs` to the polynomial of degree less than
+/// `k` that agrees with them at all but at most `max_errors(xs.len(), k)` points.
+///
+/// Returns the polynomial along with the indices at which `ys` disagrees with it, or `None` if
+/// there are too many errors.
+///
+/// Over a Galois ring the euclidean algorithm can run into a remainder whose leading coefficient
+/// is not a unit, in which case `None` is returned even if the errors could have been corrected.
+/// Evaluations without errors are always decoded.
+pub fn decode<F: Field>(xs: &[F], ys: &[F], k: usize) -> Option<(Poly<F>, Vec<usize>)> {
+    debug_assert_eq!(xs.len(), ys.len());
+    let n = xs.len();
+    if k > n {
+        return None;
+    }
+
+    let vanishing = xs
+        .iter()
+        .fold(Poly::constant(F::ONE), |acc, &x| &acc * &Poly::linear(x));
+    let interp = Poly::interpolate(xs, ys);
+
+    // Run the extended euclidean algorithm on the vanishing polynomial and the interpolation
+    // until the remainder has degree less than (n + k) / 2, keeping track of the coefficient v of
+    // the interpolation such that the remainder is v * interp modulo the vanishing polynomial.
+    let (mut r0, mut r1) = (vanishing, interp);
+    let (mut v0, mut v1) = (Poly::zero(), Poly::constant(F::ONE));
+    while r1.degree().is_some_and(|deg| 2 * deg >= n + k) {
+        let (q, r) = r0.checked_div_rem(&r1)?;
+        let v = &v0 - &(&q * &v1);
+        (r0, r1) = (r1, r);
+        (v0, v1) = (v1, v);
+    }
+
+    // The remainder is the message times the error locator v.
+    let (msg, rem) = r1.checked_div_rem(&v1)?;
+    if !rem.is_zero() || msg.degree().is_some_and(|deg| deg >= k) {
+        return None;
+    }
+
+    let errors: Vec<_> = xs
+        .iter()
+        .zip(ys.iter())
+        .enumerate()
+        .filter(|&(_, (&x, &y))| msg.eval(x) != y)
+        .map(|(i, _)| i)
+        .collect();
+
+    if errors.len() > max_errors(n, k) {
+        return None;
+    }
+
+    Some((msg, errors))
+}
@@ -1,3 +1,4 @@
+use crate::math::Field;
 use crate::sharing::PackedSharing;
 use crate::PartyID;
 use std::fmt::Debug;
@@ -26,9 +27,8 @@ pub struct ProtocolIDBuilder<'a> {
 
 impl<'a> ProtocolIDBuilder<'a> {
     /// num is an upper bound on the number of children created.
-
     pub fn new(parent_id: &'a ProtocolID, num: u64) -> Self {
-        let num_bytes = (num.next_power_of_two().ilog2() + 7) / 8;
+        let num_bytes = num.next_power_of_two().ilog2().div_ceil(8);
         ProtocolIDBuilder {
             id: parent_id,
             suffix: vec![0; num_bytes.try_into().unwrap()],
@@ -37,7 +37,7 @@ impl<'a> ProtocolIDBuilder<'a> {
 
     fn increment_bytes(&mut self, idx: usize) -> Result<(), ()> {
         if idx >= self.suffix.len() {
-            return Er(());
+            return Err(());
         }
 
         if self.suffix[idx] == u8::MAX {
@@ -92,7 +92,7 @@ impl<S: Debug, R: Debug> ProtoHandle<S, R> {
 }
 
 #[derive(Clone)]
-pub struct MPCContext<const W: u8> {
+pub struct MPCContext<F: Field> {
     pub id: PartyID,         // ID of party
     pub n: usize,            // Number of parties
     pub t: usize,            // Threshold of corrupt parties
@@ -100,6 +100,6 @@ pub struct MPCContext<const W: u8> {
     pub lpn_tau: usize,      // LPN error parameter; Bernoulli errors with bias 2^{-lnp_tau}
     pub lpn_key_len: usize,  // LPN key length
     pub lpn_mssg_len: usize, // LPN message/expanded length
-    pub pss: Arc<PackedSharing<W>>,
-    pub pss_n: Arc<PackedSharing<W>>,
+    pub pss: Arc<PackedSharing<F>>,
+    pub pss_n: Arc<PackedSharing<F>>,
 }
@@ -1,39 +1,47 @@
 use crate::circuit::WireID; //  SNGに依存する
+use crate::math::barycentric::Barycentric;
+use crate::math::galois::fft::AdditiveFft;
 use crate::math::galois::GF;
-use crate::math::lagrange_coeffs;
+use crate::math::poly::Poly;
+use crate::math::rs::{self, RsCode};
+use crate::math::{lagrange_coeffs, Field};
 use crate::ProtoErrorKind;
-use ndarray::{s, Array2, ArrayView, ArrayView1, ArrayView2};
+use ndarray::{s, Array2, ArrayView, ArrayView1, ArrayView2, Axis};
 use rand::Rng;
+use std::collections::{HashMap, HashSet};
 
-pub type PackedShare<const W: u8> = GF<W>;
+pub type PackedShare<F> = F;
 
-pub struct PackedSharing<const W: u8> {
+pub struct PackedSharing<F: Field> {
     n: usize,
     np: usize,
     l: usize,
-    share_coeffs: Array2<GF<W>>,
-    recon_coeffs: Array2<GF<W>>,
-    rand_coeffs: Array2<GF<W>>,
+    share_coeffs: Array2<F>,
+    recon_coeffs: Array2<F>,
+    rand_coeffs: Array2<F>,
 }
 
-impl<const W: u8> PackedSharing<W> {
-    pub fn default_pos(n: u32, l: u32) -> Vec<GF<W>> {
-        (n..(n + l)).map(GF::from).collect()
+impl<F: Field> PackedSharing<F> {
+    pub fn default_pos(n: u32, l: u32) -> Vec<F> {
+        (n..(n + l)).map(F::from).collect()
     }
 
-    pub fn share_pos(n: u32) -> Vec<GF<W>> {
-        (0..n).map(GF::from).collect()
+    pub fn share_pos(n: u32) -> Vec<F> {
+        (0..n).map(F::from).collect()
     }
 
-    pub fn wire_to_pos<'a, I>(n: u32, l: u32, iter: I) -> impl Iterator<Item = GF<W>> + 'a
+    pub fn wire_to_pos<'a, I>(n: u32, l: u32, iter: I) -> impl Iterator<Item = F> + 'a
     where
         I: IntoIterator<Item = WireID> + 'a,
     {
         let offset = n + l;
-        iter.into_iter().map(move |x| GF::from(x + offset))
+        iter.into_iter().map(move |x| F::from(x + offset))
     }
 
-    pub fn compute_share_coeffs(d: u32, n: u32, pos: &[GF<W>]) -> Array2<GF<W>> {
+    /// Coefficients for `share_using_coeffs` with secrets at `pos`.
+    ///
+    /// Use [`CoeffCache`] to avoid recomputing them for positions that are used repeatedly.
+    pub fn compute_share_coeffs(d: u32, n: u32, pos: &[F]) -> Array2<F> {
         let np = (d + 1) as usize;
         let sh_pos = Self::share_pos(n);
         let all_pos: Vec<_> = pos.iter().chain(sh_pos.iter()).cloned().collect();
@@ -41,28 +49,31 @@ impl<const W: u8> PackedSharing<W> {
     }
 
     pub fn share_using_coeffs<R: Rng>(
-        secrets: ArrayView1<GF<W>>,
-        coeffs: ArrayView2<GF<W>>,
+        secrets: ArrayView1<F>,
+        coeffs: ArrayView2<F>,
         l: u32,
         rng: &mut R,
-    ) -> Vec<PackedShare<W>> {
+    ) -> Vec<PackedShare<F>> {
         let np = coeffs.shape()[1];
         let l = l as usize;
 
-        let mut share: Vec<_> = (l..np).map(|_| GF::rand(rng)).collect();
+        let mut shares: Vec<_> = (l..np).map(|_| F::rand(rng)).collect();
         let points: Vec<_> = secrets
             .iter()
             .cloned()
-            .chain(std::iter::from_fn(|| Some((GF::rand(rng)))))
+            .chain(std::iter::from_fn(|| Some(F::rand(rng))))
             .take(l)
             .chain(shares.iter().cloned())
             .collect();
 
-        shares.extend(coeffs.dot(&ArrayView::from(&points)));
+        shares.extend(F::mat_vec(coeffs, ArrayView::from(&points)));
         shares
     }
 
-    pub fn compute_recon_coeffs(d: u32, n: u32, pos: &[GF<W>]) -> Array2<GF<W>> {
+    /// Coefficients for `recon_using_coeffs` with secrets at `pos`.
+    ///
+    /// Use [`CoeffCache`] to avoid recomputing them for positions that are used repeatedly.
+    pub fn compute_recon_coeffs(d: u32, n: u32, pos: &[F]) -> Array2<F> {
         let sh_pos = Self::share_pos(n);
 
         let n = n as usize;
@@ -74,43 +85,63 @@ impl<const W: u8> PackedSharing<W> {
         lagrange_coeffs(&all_pos[(l + n - np)..], &all_pos[..l])
     }
 
-    pub fn recon_using_coeffs(
-        shares: ArrayView1<GF<W>>,
-        coeffs: ArrayView2<GF<W>>,
-    ) -> Vec<PackedShare<W>> {
+    pub fn recon_using_coeffs(shares: ArrayView1<F>, coeffs: ArrayView2<F>) -> Vec<PackedShare<F>> {
         let np = coeffs.shape()[1];
         let n = shares.shape()[0];
-        coeffs.dot(&shares.slice(s![(n - np)..])).to_vec()
+        F::mat_vec(coeffs, shares.slice(s![(n - np)..])).to_vec()
     }
 
-    pub fn new(d: u32, n: u32, pos: &[GF<W>]) -> Self {
+    pub fn new(d: u32, n: u32, pos: &[F]) -> Self {
         let sh_pos = Self::share_pos(n);
         let np = d + 1;
 
         debug_assert!(np <= n);
-        debug_assert_eq!(
-            pos.iter()
-                .map(|&x| if u32::from(x) < n { 1 } else { 0 })
-                .sum::<usize>(),
-            0
+
+        // Positions come from `From<u32>`, which wraps around once the field (or the exceptional
+        // set of a Galois ring) runs out of elements.
+        let all_pos: HashSet<_> = sh_pos.iter().chain(pos.iter()).collect();
+        assert_eq!(
+            all_pos.len(),
+            sh_pos.len() + pos.len(),
+            "Positions should be distinct, the field might be too small for the number of parties."
         );
 
         let l = pos.len();
         let n = n as usize;
         let np = np as usize;
 
-        // n + l length vector consirting of positions of secret concatenated with positions of shares.
-        let all_pos: Vec<_> = pos.iter().chain(sh_pos.iter()).cloned().collect();
-
-        // polynomial defined by 1 secrets and first np - l shares.
-        let share_coeffs = lagrange_coeffs(&all_pos[..np], &all_pos[np..]);
+        // Random polynomial can be defined by using first np shares.
+        let rand_interp = Barycentric::new(&sh_pos[..np]);
+        let rand_coeffs = rand_interp.coeff_matrix(&sh_pos[np..]);
+
+        // polynomial defined by l secrets and first np - l shares, whose weights are obtained by
+        // replacing the last l shares above with the secrets. Columns are then reordered so that
+        // secrets come first.
+        let mut share_interp = rand_interp.clone();
+        for (&x, &y) in sh_pos[(np - l)..np].iter().zip(pos.iter()) {
+            share_interp.remove_point(x);
+            share_interp.add_point(y);
+        }
+        let order: Vec<_> = ((np - l)..np).chain(0..(np - l)).collect();
+        let share_coeffs = share_interp
+            .coeff_matrix(&sh_pos[(np - l)..])
+            .select(Axis(1), &order);
 
         // Using the last np shares reconstruct secret and remaining shares.
         // Remaining shares are used to check for malicious behavior.
-        let recon_coeffs = lagrange_coeffs(&all_pos[(l + n - np)..], &all_pos[..(l + n - np)]);
-
-        // Random polynomial can be defined by using first np shares.
-        let rand_coeffs = lagrange_coeffs(&sh_pos[..np], &sh_pos[np..]);
+        let mut recon_interp = rand_interp;
+        for &x in &sh_pos[..(n - np).min(np)] {
+            recon_interp.remove_point(x);
+        }
+        for &x in &sh_pos[np.max(n - np)..] {
+            recon_interp.add_point(x);
+        }
+        let recon_pos: Vec<_> = pos
+            .iter()
+            .chain(sh_pos[..(n - np)].iter())
+            .cloned()
+            .collect();
+        let recon_coeffs = recon_interp.coeff_matrix(&recon_pos);
 
         Self {
             n,
@@ -122,7 +153,7 @@ impl<const W: u8> PackedSharing<W> {
         }
     }
 
-    pub fn share<R: Rng>(&self, secrets: ArrayView1<GF<W>>, rng: &mut R) -> Vec<PackedShare<W>> {
+    pub fn share<R: Rng>(&self, secrets: ArrayView1<F>, rng: &mut R) -> Vec<PackedShare<F>> {
         Self::share_using_coeffs(
             secrets,
             self.share_coeffs.view(),
@@ -131,30 +162,35 @@ impl<const W: u8> PackedSharing<W> {
         )
     }
 
-    pub fn rand<R: Rng>(&self, rng: &mut R) -> Vec<PackedShare<W>> {
-        let mut shares: Vec<_> = (0..self.np).map(|_| GF::rand(rng)).collect();
+    pub fn rand<R: Rng>(&self, rng: &mut R) -> Vec<PackedShare<F>> {
+        let mut shares: Vec<_> = (0..self.np).map(|_| F::rand(rng)).collect();
+        let rest = F::mat_vec(self.rand_coeffs.view(), ArrayView::from(&shares));
+        shares.extend(rest);
+        shares
     }
 
-    pub fn semihon_recon(&self, shares: ArrayView<GF<W>>) -> Vec<GF<W>> {
+    pub fn semihon_recon(&self, shares: ArrayView1<F>) -> Vec<F> {
         debug_assert_eq!(shares.len(), self.n);
 
-        self.recon_coeffs
-            .slice(s![..self.l, ..])
-            .dot(&shares.slice(s![(self.n - self.np)..]))
-            .to_vec()
+        F::mat_vec(
+            self.recon_coeffs.slice(s![..self.l, ..]),
+            shares.slice(s![(self.n - self.np)..]),
+        )
+        .to_vec()
     }
 
-    pub fn recon(&self, shares: ArrayView<GF<W>>) -> Result<Vec<GF<W>>, ProtoErrorKind> {
+    pub fn recon(&self, shares: ArrayView1<F>) -> Result<Vec<F>, ProtoErrorKind> {
         if shares.len() != self.n {
             return Err(ProtoErrorKind::Other(""));
         }
 
-        let recon_vals = self
-            .recon_coeffs
-            .dot(&shares.slice(s![(self.n - self.np)..]))
-            .to_vec();
+        let recon_vals = F::mat_vec(
+            self.recon_coeffs.view(),
+            shares.slice(s![(self.n - self.np)..]),
+        )
+        .to_vec();
 
-        for (i, &v) in recon_vals[self.l..].iter().enumrate() {
+        for (i, &v) in recon_vals[self.l..].iter().enumerate() {
             if v != shares[i] {
                 return Err(ProtoErrorKind::MaliciousBehavior);
             }
@@ -162,7 +198,48 @@ impl<const W: u8> PackedSharing<W> {
         Ok(recon_vals[..self.l].to_vec())
     }
 
-    pub fn recon_coeffs(&self) -> ArrayView2<GF<W>> {
+    /// Reed-Solomon code formed by consistent sharings, whose syndromes can be used to check
+    /// batches of sharings without reconstructing them.
+    pub fn rs_code(&self) -> RsCode<F> {
+        RsCode::new(&Self::share_pos(self.num_parties()), self.np)
+    }
+
+    /// Reconstruct the secrets while correcting up to (n - d - 1) / 2 wrong shares, returning them
+    /// along with the indices of the parties whose shares were wrong. When there is not enough
+    /// redundancy to correct any errors, wrong shares are only detected as in `recon`.
+    ///
+    /// Over a Galois ring, correction can fail (see [`rs::decode`]), in which case the wrong
+    /// shares are still detected and an error is returned.
+    pub fn robust_recon(
+        &self,
+        shares: ArrayView1<F>,
+    ) -> Result<(Vec<F>, Vec<usize>), ProtoErrorKind> {
+        if shares.len() != self.n {
+            return Err(ProtoErrorKind::Other(""));
+        }
+
+        if rs::max_errors(self.n, self.np) == 0 {
+            return self.recon(shares).map(|secrets| (secrets, Vec::new()));
+        }
+
+        let sh_pos = Self::share_pos(self.num_parties());
+        let (poly, cheaters) = rs::decode(&sh_pos, &shares.to_vec(), self.np)
+            .ok_or(ProtoErrorKind::MaliciousBehavior)?;
+
+        let corrected = poly.eval_many(&sh_pos);
+        Ok((self.semihon_recon(ArrayView::from(&corrected)), cheaters))
+    }
+
+    /// Polynomial through all the shares, whose degree is at most `degree()` if they are
+    /// consistent and whose evaluations at the secret positions are the secrets.
+    pub fn interpolate(&self, shares: ArrayView1<F>) -> Poly<F> {
+        debug_assert_eq!(shares.len(), self.n);
+
+        let sh_pos = Self::share_pos(self.num_parties());
+        Poly::interpolate(&sh_pos, &shares.to_vec())
+    }
+
+    pub fn recon_coeffs(&self) -> ArrayView2<'_, F> {
         self.recon_coeffs.slice(s![..self.l, ..])
     }
 
@@ -178,3 +255,214 @@ impl<const W: u8> PackedSharing<W> {
         (self.np - 1).try_into().unwrap()
     }
 }
+
+/// Coefficient matrices of [`PackedSharing`] for given parameters and secret positions, computed
+/// on first use.
+///
+/// The cache is owned by the caller, so matrices for one-off position sets are freed when it is
+/// dropped or cleared.
+#[derive(Debug, Clone)]
+pub struct CoeffCache<F: Field> {
+    share: HashMap<(u32, u32, Vec<F>), Array2<F>>,
+    recon: HashMap<(u32, u32, Vec<F>), Array2<F>>,
+}
+
+impl<F: Field> Default for CoeffCache<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Field> CoeffCache<F> {
+    /// Empty cache.
+    pub fn new() -> Self {
+        Self {
+            share: HashMap::new(),
+            recon: HashMap::new(),
+        }
+    }
+
+    /// Same as [`PackedSharing::compute_share_coeffs`], reusing the matrix computed by a previous
+    /// call with the same arguments.
+    pub fn share_coeffs(&mut self, d: u32, n: u32, pos: &[F]) -> &Array2<F> {
+        self.share
+            .entry((d, n, pos.to_vec()))
+            .or_insert_with(|| PackedSharing::compute_share_coeffs(d, n, pos))
+    }
+
+    /// Same as [`PackedSharing::compute_recon_coeffs`], reusing the matrix computed by a previous
+    /// call with the same arguments.
+    pub fn recon_coeffs(&mut self, d: u32, n: u32, pos: &[F]) -> &Array2<F> {
+        self.recon
+            .entry((d, n, pos.to_vec()))
+            .or_insert_with(|| PackedSharing::compute_recon_coeffs(d, n, pos))
+    }
+
+    /// Number of cached matrices.
+    pub fn len(&self) -> usize {
+        self.share.len() + self.recon.len()
+    }
+
+    /// Returns true if no matrices are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all cached matrices.
+    pub fn clear(&mut self) {
+        self.share.clear();
+        self.recon.clear();
+    }
+}
+
+/// Packed secret sharing over GF(2^W) with O(n log n) sharing and reconstruction.
+///
+/// Shares are placed on the points GF::from(i) for i < n and secrets on a coset of a subspace
+/// right after them, so that polynomials can be evaluated and interpolated using additive FFTs
+/// instead of dense lagrange coefficient matrices. This requires the number of secrets rounded up
+/// to a power of two to be at most d + 1, and d + 1 rounded up to a power of two to be at most n.
+pub struct FftPackedSharing<const W: u8> {
+    n: usize,
+    d: usize,
+    l: usize,
+    /// Number of secrets rounded up to a power of two.
+    lp: usize,
+    /// Number of shares used for reconstruction i.e., d + 1 rounded up to a power of two.
+    m: usize,
+    /// Index of the first secret position, which is a multiple of `lp`.
+    offset: usize,
+    fft: AdditiveFft<W>,
+}
+
+impl<const W: u8> FftPackedSharing<W> {
+    pub fn new(d: u32, n: u32, l: u32) -> Self {
+        let (d, n, l) = (d as usize, n as usize, l as usize);
+        let lp = l.next_power_of_two();
+        let m = (d + 1).next_power_of_two();
+        let offset = n.next_multiple_of(lp);
+        let size = (offset + lp).next_power_of_two();
+
+        assert!(lp <= d + 1, "Too many secrets for the degree.");
+        assert!(m <= n, "Too few parties for the degree.");
+        assert!(
+            size <= GF::<W>::ORDER as usize,
+            "Field is too small for the number of parties."
+        );
+
+        Self {
+            n,
+            d,
+            l,
+            lp,
+            m,
+            offset,
+            fft: AdditiveFft::new(size.trailing_zeros() as usize),
+        }
+    }
+
+    pub fn share_pos(&self) -> Vec<GF<W>> {
+        (0..self.n).map(|i| GF::from(i as u32)).collect()
+    }
+
+    pub fn secret_pos(&self) -> Vec<GF<W>> {
+        (self.offset..(self.offset + self.l))
+            .map(|i| GF::from(i as u32))
+            .collect()
+    }
+
+    /// Evaluations at all points of a polynomial given by its coefficients in the novel basis.
+    fn evaluate(&self, mut coeffs: Vec<GF<W>>) -> Vec<GF<W>> {
+        coeffs.resize(self.fft.max_size(), GF::ZERO);
+        self.fft.fft(&mut coeffs, GF::ZERO);
+        coeffs
+    }
+
+    pub fn share<R: Rng>(
+        &self,
+        secrets: ArrayView1<GF<W>>,
+        rng: &mut R,
+    ) -> Vec<PackedShare<GF<W>>> {
+        debug_assert!(secrets.len() <= self.l);
+
+        // Random polynomial with zero coefficients below lp, which is then shifted by the
+        // polynomial of degree less than lp that makes the sum agree with the secrets.
+        let mut coeffs = vec![GF::ZERO; self.lp];
+        coeffs.extend((self.lp..=self.d).map(|_| GF::rand(rng)));
+        let mut evals = self.evaluate(coeffs);
+
+        let secret_evals = &evals[self.offset..(self.offset + self.lp)];
+        let mut low: Vec<_> = secrets
+            .iter()
+            .cloned()
+            .chain(std::iter::from_fn(|| Some(GF::rand(rng))))
+            .zip(secret_evals.iter())
+            .map(|(s, &v)| s - v)
+            .take(self.lp)
+            .collect();
+        self.fft.ifft(&mut low, GF::from(self.offset as u32));
+
+        for (v, l) in evals.iter_mut().zip(self.evaluate(low)) {
+            *v += l;
+        }
+
+        evals.truncate(self.n);
+        evals
+    }
+
+    pub fn rand<R: Rng>(&self, rng: &mut R) -> Vec<PackedShare<GF<W>>> {
+        let coeffs = (0..=self.d).map(|_| GF::rand(rng)).collect();
+        let mut evals = self.evaluate(coeffs);
+        evals.truncate(self.n);
+        evals
+    }
+
+    /// Coefficients in the novel basis of the polynomial through the first m shares.
+    fn interpolate(&self, shares: ArrayView1<GF<W>>) -> Vec<GF<W>> {
+        let mut coeffs: Vec<_> = shares.iter().take(self.m).cloned().collect();
+        self.fft.ifft(&mut coeffs, GF::ZERO);
+        coeffs
+    }
+
+    pub fn semihon_recon(&self, shares: ArrayView1<GF<W>>) -> Vec<GF<W>> {
+        debug_assert_eq!(shares.len(), self.n);
+
+        let evals = self.evaluate(self.interpolate(shares));
+        evals[self.offset..(self.offset + self.l)].to_vec()
+    }
+
+    pub fn recon(&self, shares: ArrayView1<GF<W>>) -> Result<Vec<GF<W>>, ProtoErrorKind> {
+        if shares.len() != self.n {
+            return Err(ProtoErrorKind::Other(""));
+        }
+
+        // The first m shares should define a polynomial of degree at most d, which should agree
+        // with the remaining shares.
+        let coeffs = self.interpolate(shares);
+        if coeffs[(self.d + 1)..].iter().any(|&c| c != GF::ZERO) {
+            return Err(ProtoErrorKind::MaliciousBehavior);
+        }
+
+        let evals = self.evaluate(coeffs);
+        if evals[self.m..self.n]
+            .iter()
+            .zip(shares.slice(s![self.m..]).iter())
+            .any(|(a, b)| a != b)
+        {
+            return Err(ProtoErrorKind::MaliciousBehavior);
+        }
+
+        Ok(evals[self.offset..(self.offset + self.l)].to_vec())
+    }
+
+    pub fn num_parties(&self) -> u32 {
+        self.n.try_into().unwrap()
+    }
+
+    pub fn num_secrets(&self) -> u32 {
+        self.l.try_into().unwrap()
+    }
+
+    pub fn degree(&self) -> u32 {
+        self.d.try_into().unwrap()
+    }
+}
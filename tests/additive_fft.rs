@@ -0,0 +1,69 @@
+//! Checks the additive FFT and packed sharing on subspace points.
+use ndarray::ArrayView;
+use pss::math::galois::fft::AdditiveFft;
+use pss::math::galois::GF;
+use pss::math::poly::Poly;
+use pss::sharing::FftPackedSharing;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+#[test]
+fn fft_roundtrip() {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    let fft = AdditiveFft::<16>::new(8);
+
+    for k in 0..=8 {
+        let data: Vec<_> = (0..(1 << k)).map(|_| GF::<16>::rand(&mut rng)).collect();
+        let shift = GF::from(rng.gen_range(0..256u32) << 8);
+
+        let mut vals = data.clone();
+        fft.fft(&mut vals, shift);
+        fft.ifft(&mut vals, shift);
+        assert_eq!(vals, data);
+    }
+}
+
+#[test]
+fn fft_evaluates_low_degree_polynomial() {
+    let mut rng = ChaCha12Rng::seed_from_u64(1);
+    let fft = AdditiveFft::<16>::new(6);
+
+    // Coefficients of degree less than 16, evaluated on 64 points.
+    let mut vals: Vec<_> = (0..16).map(|_| GF::<16>::rand(&mut rng)).collect();
+    vals.resize(64, GF::ZERO);
+    fft.fft(&mut vals, GF::ZERO);
+
+    let xs: Vec<_> = (0..64u32).map(GF::from).collect();
+    let poly = Poly::interpolate(&xs[..16], &vals[..16]);
+    assert_eq!(poly.eval_many(&xs), vals);
+}
+
+#[test]
+fn fft_sharing() {
+    let mut rng = ChaCha12Rng::seed_from_u64(2);
+    let (d, n, l) = (20, 40, 10);
+    let pss = FftPackedSharing::<16>::new(d, n, l);
+
+    let secrets: Vec<_> = (0..l).map(|_| GF::<16>::rand(&mut rng)).collect();
+    let mut shares = pss.share(ArrayView::from(&secrets), &mut rng);
+    assert_eq!(shares.len(), n as usize);
+
+    // Shares and secrets lie on a polynomial of degree at most d.
+    let xs = pss.share_pos();
+    let poly = Poly::interpolate(&xs[..=(d as usize)], &shares[..=(d as usize)]);
+    assert_eq!(poly.eval_many(&xs), shares);
+    assert_eq!(poly.eval_many(&pss.secret_pos()), secrets);
+
+    assert_eq!(pss.semihon_recon(ArrayView::from(&shares)), secrets);
+    assert_eq!(pss.recon(ArrayView::from(&shares)).unwrap(), secrets);
+
+    let rand = pss.rand(&mut rng);
+    let poly = Poly::interpolate(&xs[..=(d as usize)], &rand[..=(d as usize)]);
+    assert_eq!(poly.eval_many(&xs), rand);
+
+    shares[n as usize - 1] += GF::ONE;
+    assert!(pss.recon(ArrayView::from(&shares)).is_err());
+    shares[n as usize - 1] += GF::ONE;
+    shares[0] += GF::ONE;
+    assert!(pss.recon(ArrayView::from(&shares)).is_err());
+}
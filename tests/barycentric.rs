@@ -0,0 +1,96 @@
+//! Checks barycentric interpolation against polynomial interpolation and fresh pre-computation.
+mod common;
+
+use ndarray::Array2;
+use pss::math::barycentric::Barycentric;
+use pss::math::poly::Poly;
+use pss::math::prime::Mersenne61;
+use pss::math::Field;
+use pss::sharing::{CoeffCache, PackedSharing};
+use rand::Rng;
+use rand_chacha::ChaCha12Rng;
+
+/// Lagrange coefficients computed directly as \prod_{j != i} (v - x_j) / (x_i - x_j), with one row
+/// per point in `npos`.
+fn direct_coeffs<F: Field>(cpos: &[F], npos: &[F]) -> Array2<F> {
+    Array2::from_shape_fn((npos.len(), cpos.len()), |(r, i)| {
+        cpos.iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold(F::ONE, |acc, (_, &x)| acc * (npos[r] - x) / (cpos[i] - x))
+    })
+}
+
+fn check_interpolation<F: Field>(rng: &mut ChaCha12Rng) {
+    let points: Vec<F> = (0..30u32).map(F::from).collect();
+
+    let interp = Barycentric::new(&points[..12]);
+    let values: Vec<_> = (0..12).map(|_| F::rand(rng)).collect();
+    let poly = Poly::interpolate(&points[..12], &values);
+    for &x in &points {
+        assert_eq!(interp.eval(&values, x), poly.eval(x));
+    }
+    assert_eq!(
+        interp.coeff_matrix(&points[12..]),
+        direct_coeffs(&points[..12], &points[12..])
+    );
+
+    // At an interpolation point, the coefficients select its value.
+    let mut unit = vec![F::ZERO; 12];
+    unit[5] = F::ONE;
+    assert_eq!(interp.coeffs(points[5]), unit);
+
+    // Incremental updates agree with computing the weights from scratch.
+    let mut cur: Vec<_> = points[..12].to_vec();
+    let mut inc = interp;
+    for _ in 0..20 {
+        if rng.gen() && cur.len() > 1 {
+            let x = cur.remove(rng.gen_range(0..cur.len()));
+            assert!(inc.remove_point(x));
+        } else if let Some(&x) = points.iter().find(|x| !cur.contains(x)) {
+            cur.push(x);
+            inc.add_point(x);
+        }
+        assert_eq!(inc, Barycentric::new(&cur));
+    }
+    assert!(!inc.remove_point(F::from(1000u32)));
+}
+
+// The points are in the exceptional set of the ring, so all weights are defined.
+common::field_tests!(check_interpolation, rings);
+
+#[test]
+fn cached_sharing_coeffs() {
+    type F = Mersenne61;
+    let (d, n) = (7, 16);
+    let pos = PackedSharing::<F>::default_pos(n, 4);
+    let all_pos: Vec<F> = pos
+        .iter()
+        .cloned()
+        .chain(PackedSharing::<F>::share_pos(n))
+        .collect();
+
+    let share = PackedSharing::<F>::compute_share_coeffs(d, n, &pos);
+    assert_eq!(share, direct_coeffs(&all_pos[..8], &all_pos[8..]));
+    let recon = PackedSharing::<F>::compute_recon_coeffs(d, n, &pos);
+    assert_eq!(recon, direct_coeffs(&all_pos[12..], &all_pos[..4]));
+
+    let mut cache = CoeffCache::new();
+    let first: *const _ = cache.share_coeffs(d, n, &pos);
+    assert_eq!(cache.share_coeffs(d, n, &pos), &share);
+    assert!(std::ptr::eq(first, cache.share_coeffs(d, n, &pos)));
+    assert_eq!(cache.recon_coeffs(d, n, &pos), &recon);
+    assert_eq!(cache.len(), 2);
+
+    // Different positions or parameters are cached separately.
+    let other = PackedSharing::<F>::default_pos(n + 1, 4);
+    assert_eq!(
+        cache.share_coeffs(d, n, &other),
+        &PackedSharing::<F>::compute_share_coeffs(d, n, &other)
+    );
+    cache.share_coeffs(d - 1, n, &pos);
+    assert_eq!(cache.len(), 4);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,48 @@
+//! Checks that bit-packed binary matrices agree with dense products.
+mod common;
+
+use common::rand_mat;
+use ndarray::Array2;
+use pss::math::binary::BinaryMatrix;
+use pss::math::prime::Mersenne61;
+use pss::math::Field;
+use rand::Rng;
+use rand_chacha::ChaCha12Rng;
+
+fn check_products<F: Field>(rng: &mut ChaCha12Rng) {
+    // Dimensions around word boundaries.
+    for (rows, cols) in [(1, 1), (5, 63), (7, 64), (20, 65), (130, 200)] {
+        let dense =
+            Array2::from_shape_simple_fn((rows, cols), || if rng.gen() { F::ONE } else { F::ZERO });
+        let packed = BinaryMatrix::from_dense(dense.view()).unwrap();
+        assert_eq!(packed.dim(), (rows, cols));
+        assert_eq!(packed.to_dense::<F>(), dense);
+        assert_eq!(packed.transpose().to_dense::<F>(), dense.t());
+
+        let v = rand_mat::<F, _>(cols, 1, rng);
+        assert_eq!(packed.mul_vec(v.column(0)), dense.dot(&v.column(0)));
+
+        let m = rand_mat::<F, _>(cols, 9, rng);
+        assert_eq!(packed.mul_mat(m.view()), dense.dot(&m));
+    }
+
+    let mut dense = Array2::from_elem((3, 3), F::ZERO);
+    dense[[1, 2]] = F::from(2u32);
+    assert!(BinaryMatrix::from_dense(dense.view()).is_err());
+}
+
+// Products only add entries, so they also work over Galois rings.
+common::field_tests!(check_products, rings);
+
+#[test]
+fn get_and_set() {
+    let mut mat = BinaryMatrix::zeros(3, 100);
+    mat.set(2, 70, true);
+    mat.set(0, 0, true);
+    assert!(mat.get(2, 70) && mat.get(0, 0));
+    assert!(!mat.get(1, 70));
+
+    mat.set(2, 70, false);
+    assert!(!mat.get(2, 70));
+    assert_eq!(mat.to_dense::<Mersenne61>().sum(), Mersenne61::ONE);
+}
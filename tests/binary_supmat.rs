@@ -0,0 +1,47 @@
+//! Checks generation and storage of binary super-invertible matrices.
+use pss::math::galois::GF;
+use pss::math::linalg::{check_super_invertible_for, MinorCheck};
+use pss::math::prime::Mersenne61;
+use pss::math::{
+    binary_super_inv_matrix, gen_binary_super_inv_matrix, write_binary_super_inv_matrix,
+};
+
+#[test]
+fn generate_and_roundtrip() {
+    let (n, t, l) = (12, 3, 4);
+
+    let mat = gen_binary_super_inv_matrix::<GF<8>>(n, l, n - t, 0, MinorCheck::Exact).unwrap();
+    assert_eq!(mat.dim(), (l, n));
+    assert!(mat.iter().all(|&v| v == GF::ZERO || v == GF::ONE));
+    assert_eq!(
+        check_super_invertible_for(mat.view(), n - t, MinorCheck::Exact),
+        Ok(())
+    );
+
+    // The same seed gives the same matrix.
+    let again = gen_binary_super_inv_matrix::<GF<8>>(n, l, n - t, 0, MinorCheck::Exact).unwrap();
+    assert_eq!(mat, again);
+
+    let path = std::env::temp_dir().join(format!("pss_binary_supmat_{}.txt", std::process::id()));
+    write_binary_super_inv_matrix(mat.view(), &path).unwrap();
+    let read = binary_super_inv_matrix::<GF<8>>(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(read, mat);
+}
+
+#[test]
+fn prime_field() {
+    let mode = MinorCheck::Randomized {
+        trials: 50,
+        seed: 0,
+    };
+    let mat = gen_binary_super_inv_matrix::<Mersenne61>(40, 20, 20, 1, mode).unwrap();
+    assert_eq!(check_super_invertible_for(mat.view(), 20, mode), Ok(()));
+}
+
+#[test]
+fn infeasible_dimensions() {
+    // Any 3 of 10 binary vectors in GF(2)^3 cannot all be independent.
+    assert!(gen_binary_super_inv_matrix::<GF<8>>(10, 3, 3, 0, MinorCheck::Exact).is_err());
+    assert!(gen_binary_super_inv_matrix::<GF<8>>(10, 4, 3, 0, MinorCheck::Exact).is_err());
+}
@@ -0,0 +1,179 @@
+//! Checks composition, inversion, routing and serialization of combinations.
+use ndarray::{s, Array1, Array2, ArrayView};
+use pss::math::Combination;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+fn rand_perm<R: Rng>(n: usize, rng: &mut R) -> Combination {
+    let mut map: Vec<_> = (0..n).collect();
+    map.shuffle(rng);
+    Combination::new(map)
+}
+
+#[test]
+fn compose_and_invert() {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    let inp: Vec<u32> = (0..30).map(|_| rng.gen()).collect();
+    let inp = ArrayView::from(&inp);
+
+    let first = Combination::new((0..20).map(|_| rng.gen_range(0..30)).collect());
+    let second = Combination::new((0..25).map(|_| rng.gen_range(0..20)).collect());
+    let composed = second.compose(&first);
+    assert_eq!(
+        composed.apply(inp),
+        second.apply(ArrayView::from(&first.apply(inp)))
+    );
+    assert!(!first.is_permutation());
+    assert_eq!(first.inverse(), None);
+    assert_eq!(first.cycles(), None);
+    assert!(first.benes().is_none());
+
+    let perm = rand_perm(30, &mut rng);
+    let inv = perm.inverse().unwrap();
+    let identity = Combination::new((0..30).collect());
+    assert_eq!(perm.compose(&inv), identity);
+    assert_eq!(inv.compose(&perm), identity);
+    assert!(!Combination::new(vec![0, 2, 2]).is_permutation());
+    assert!(!Combination::new(vec![0, 3, 1]).is_permutation());
+}
+
+#[test]
+fn cycles() {
+    let perm = Combination::new(vec![2, 0, 1, 3, 5, 4]);
+    assert_eq!(
+        perm.cycles().unwrap(),
+        vec![vec![0, 2, 1], vec![3], vec![4, 5]]
+    );
+
+    let mut rng = ChaCha12Rng::seed_from_u64(1);
+    let perm = rand_perm(50, &mut rng);
+    let cycles = perm.cycles().unwrap();
+    assert_eq!(cycles.iter().map(Vec::len).sum::<usize>(), 50);
+    for cycle in &cycles {
+        for (j, &c) in cycle.iter().enumerate() {
+            assert_eq!(perm.map()[c], cycle[(j + 1) % cycle.len()]);
+        }
+    }
+}
+
+#[test]
+fn benes_network() {
+    let mut rng = ChaCha12Rng::seed_from_u64(2);
+    for n in [0, 1, 2, 3, 4, 7, 8, 16, 33, 100, 256] {
+        let inp: Vec<u64> = (0..n).map(|_| rng.gen()).collect();
+        for _ in 0..10 {
+            let perm = rand_perm(n, &mut rng);
+            let net = perm.benes().unwrap();
+            assert_eq!(net.len(), n);
+            assert_eq!(net.size(), n.next_power_of_two());
+            assert_eq!(net.apply(&inp), perm.apply(ArrayView::from(&inp)));
+        }
+    }
+
+    let net = rand_perm(16, &mut rng).benes().unwrap();
+    assert_eq!(net.depth(), 7);
+}
+
+#[test]
+fn rows_and_cols() {
+    let m = Array2::from_shape_fn((4, 3), |(i, j)| 10 * i + j);
+    let comb = Combination::new(vec![2, 0, 2]);
+
+    let rows = comb.apply_rows(m.view());
+    assert_eq!(rows.dim(), (3, 3));
+    assert_eq!(rows.row(0), m.row(2));
+    assert_eq!(rows.row(1), m.row(0));
+
+    let cols = comb.apply_cols(m.view());
+    assert_eq!(cols.dim(), (4, 3));
+    for r in 0..4 {
+        assert_eq!(cols.row(r).to_vec(), comb.apply(m.row(r)));
+    }
+}
+
+#[test]
+fn serialization() {
+    let mut rng = ChaCha12Rng::seed_from_u64(3);
+    let perm = rand_perm(40, &mut rng);
+    let bytes = bincode::serialize(&perm).unwrap();
+    assert_eq!(bincode::deserialize::<Combination>(&bytes).unwrap(), perm);
+
+    let net = perm.benes().unwrap();
+    let bytes = bincode::serialize(&net).unwrap();
+    let restored: pss::math::benes::BenesNetwork = bincode::deserialize(&bytes).unwrap();
+    let inp = Array1::from_shape_fn(40, |i| i);
+    assert_eq!(
+        restored.apply(inp.as_slice().unwrap()),
+        perm.apply(inp.view())
+    );
+}
+
+#[test]
+fn batched_gather() {
+    let mut rng = ChaCha12Rng::seed_from_u64(4);
+    let inp = Array2::from_shape_fn((50, 30), |_| rng.gen::<u32>());
+    let comb = Combination::new((0..45).map(|_| rng.gen_range(0..30)).collect());
+
+    let mut out = Array2::zeros((50, 45));
+    comb.apply_batch_into(inp.view(), out.view_mut());
+    for (o, v) in out.rows().into_iter().zip(inp.rows()) {
+        assert_eq!(o.to_vec(), comb.apply(v));
+    }
+
+    // Strided inputs and outputs.
+    let mut out_t = Array2::zeros((45, 50));
+    comb.apply_batch_into(inp.view(), out_t.view_mut().reversed_axes());
+    assert_eq!(out_t.t(), out);
+
+    let inps: Vec<_> = inp.columns().into_iter().collect();
+    let mut out = Array2::zeros((30, 45));
+    comb.apply_many_into(&inps, out.view_mut());
+    for (o, v) in out.rows().into_iter().zip(inps.iter()) {
+        assert_eq!(o.to_vec(), comb.apply(v.view()));
+    }
+
+    let mut single = Array1::zeros(45);
+    comb.apply_into(inp.slice(s![3, ..]), single.view_mut());
+    assert_eq!(single.to_vec(), comb.apply(inp.row(3)));
+}
+
+#[test]
+#[should_panic(expected = "Dimension mismatch.")]
+fn gather_length_mismatch() {
+    let comb = Combination::new(vec![2, 0, 1]);
+    let mut out = Array1::zeros(2);
+    comb.apply_into(Array1::<u32>::zeros(3).view(), out.view_mut());
+}
+
+#[test]
+#[should_panic(expected = "Dimension mismatch.")]
+fn benes_length_mismatch() {
+    let net = Combination::new(vec![2, 0, 1]).benes().unwrap();
+    net.apply(&[1, 2]);
+}
+
+#[test]
+fn inconsistent_benes_network() {
+    use pss::math::benes::BenesNetwork;
+
+    let net = Combination::new(vec![3, 0, 4, 1, 2]).benes().unwrap();
+    let bytes = bincode::serialize(&net).unwrap();
+    assert_eq!(bincode::deserialize::<BenesNetwork>(&bytes).unwrap(), net);
+
+    // The length no longer fits the size of 8.
+    let mut bad = bytes.clone();
+    bad[0] = 9;
+    assert!(bincode::deserialize::<BenesNetwork>(&bad).is_err());
+
+    // Fewer input switches than the size requires.
+    let mut bad = bytes.clone();
+    bad[16] = 3;
+    bad.remove(24);
+    assert!(bincode::deserialize::<BenesNetwork>(&bad).is_err());
+
+    // Crossing the output switch of positions 4 and 5 routes the padding into position 4.
+    let mut bad = bytes;
+    bad[38] ^= 1;
+    assert!(bincode::deserialize::<BenesNetwork>(&bad).is_err());
+}
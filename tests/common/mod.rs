@@ -0,0 +1,45 @@
+//! Helpers shared by the integration tests.
+//!
+//! Each test binary only uses some of them.
+#![allow(dead_code)]
+use ndarray::Array2;
+use pss::math::Field;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+/// Generator with a fixed seed, so that failures are reproducible.
+pub fn rng() -> ChaCha12Rng {
+    ChaCha12Rng::seed_from_u64(0)
+}
+
+/// Matrix with uniformly random entries.
+pub fn rand_mat<F: Field, R: Rng>(rows: usize, cols: usize, rng: &mut R) -> Array2<F> {
+    Array2::from_shape_simple_fn((rows, cols), || F::rand(rng))
+}
+
+/// Defines tests running `$check::<F>(&mut rng)` for a binary field (`binary_field` over
+/// `GF<16>`) and a prime field (`prime_field` over `Mersenne61`). Passing `rings` also defines
+/// `galois_ring` over `GR<64, 8>`, for checks that don't need division by arbitrary elements.
+macro_rules! field_tests {
+    ($check:ident) => {
+        #[test]
+        fn binary_field() {
+            $check::<::pss::math::galois::GF<16>>(&mut $crate::common::rng());
+        }
+
+        #[test]
+        fn prime_field() {
+            $check::<::pss::math::prime::Mersenne61>(&mut $crate::common::rng());
+        }
+    };
+    ($check:ident, rings) => {
+        $crate::common::field_tests!($check);
+
+        #[test]
+        fn galois_ring() {
+            $check::<::pss::math::ring::GR<64, 8>>(&mut $crate::common::rng());
+        }
+    };
+}
+
+pub(crate) use field_tests;
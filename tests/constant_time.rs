@@ -0,0 +1,210 @@
+//! Checks the constant-time field arithmetic against the pure-Rust backend, along with a
+//! dudect-style timing harness.
+//!
+//! The timing tests follow "Dude, is my code constant time?" by Reparaz et al.: inputs are split
+//! into a class with a fixed operand and a class with random operands, the running time of each
+//! measurement is recorded and Welch's t-test checks whether the two classes can be told apart.
+//! They are ignored by default since they take a while and need a quiet machine; run them with
+//! `cargo test --release --test constant_time -- --ignored`, adding `--features constant-time` to
+//! also time the public operators of `GF<W>`.
+use pss::math::galois::{ct, native};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use std::hint::black_box;
+use std::time::Instant;
+
+fn check_width(w: u8) {
+    ct::init(w).unwrap();
+    native::init(w).unwrap();
+
+    let order = 1u32 << w;
+    let mut rng = ChaCha12Rng::seed_from_u64(w.into());
+    let pairs: Vec<(u32, u32)> = if w <= 6 {
+        (0..order)
+            .flat_map(|x| (0..order).map(move |y| (x, y)))
+            .collect()
+    } else {
+        (0..10_000)
+            .map(|_| (rng.gen_range(0..order), rng.gen_range(0..order)))
+            .collect()
+    };
+
+    for (x, y) in pairs {
+        assert_eq!(
+            ct::mul(x, y, w),
+            native::mul(x, y, w),
+            "mul {x} {y} for W = {w}"
+        );
+        assert_eq!(
+            ct::div(x, y, w),
+            native::div(x, y, w),
+            "div {x} {y} for W = {w}"
+        );
+    }
+
+    assert_eq!(ct::inv(0, w), 0);
+    assert_eq!(ct::inv(1, w), 1);
+}
+
+#[test]
+fn matches_native() {
+    for w in 1..=native::MAX_WIDTH {
+        check_width(w);
+    }
+    assert!(ct::init(0).is_err());
+    assert!(ct::init(native::MAX_WIDTH + 1).is_err());
+}
+
+#[cfg(feature = "constant-time")]
+#[test]
+fn field_uses_constant_time_backend() {
+    use pss::math::galois::GF;
+
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let (x, y) = (GF::<20>::rand(&mut rng), GF::<20>::rand(&mut rng));
+        if y != GF::ZERO {
+            assert_eq!((x / y) * y, x);
+        }
+    }
+}
+
+/// Threshold on the t-statistic above which the classes are considered distinguishable, as in
+/// dudect.
+const T_THRESHOLD: f64 = 10.0;
+
+const NUM_MEASUREMENTS: usize = 200_000;
+
+/// Operations timed per measurement, to stay well above the timer resolution.
+const BATCH: usize = 64;
+
+/// Running mean and variance of the measurements of both classes using Welford's algorithm.
+#[derive(Default)]
+struct Welch {
+    n: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl Welch {
+    fn push(&mut self, class: usize, x: f64) {
+        self.n[class] += 1.0;
+        let delta = x - self.mean[class];
+        self.mean[class] += delta / self.n[class];
+        self.m2[class] += delta * (x - self.mean[class]);
+    }
+
+    fn t(&self) -> f64 {
+        let var = |c: usize| self.m2[c] / (self.n[c] - 1.0);
+        (self.mean[0] - self.mean[1]) / (var(0) / self.n[0] + var(1) / self.n[1]).sqrt()
+    }
+}
+
+/// Times `op` on batches whose second operand is either `fixed` or drawn using `sample`, returning
+/// the t-statistic of the two classes.
+fn measure<T, S, F>(fixed: T, sample: S, op: F) -> f64
+where
+    T: Copy,
+    S: Fn(&mut ChaCha12Rng) -> T,
+    F: Fn(T, T) -> T,
+{
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+
+    let mut samples = Vec::with_capacity(NUM_MEASUREMENTS);
+    for _ in 0..NUM_MEASUREMENTS {
+        // Inputs are prepared before timing so that sampling doesn't affect the measurement, and
+        // in the same way for both classes so that they leave the caches in the same state.
+        let class = rng.gen_range(0..2);
+        let inputs: Vec<(T, T)> = (0..BATCH)
+            .map(|_| {
+                let (x, y) = (sample(&mut rng), sample(&mut rng));
+                (x, if class == 0 { fixed } else { y })
+            })
+            .collect();
+
+        let start = Instant::now();
+        for &(x, y) in inputs.iter() {
+            black_box(op(black_box(x), black_box(y)));
+        }
+        samples.push((class, start.elapsed().as_nanos() as f64));
+    }
+
+    // Drop the slowest measurements, which are dominated by interrupts and context switches.
+    let mut sorted: Vec<_> = samples.iter().map(|&(_, t)| t).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let cutoff = sorted[sorted.len() * 9 / 10];
+
+    let mut welch = Welch::default();
+    for (class, t) in samples {
+        if t <= cutoff {
+            welch.push(class, t);
+        }
+    }
+
+    welch.t()
+}
+
+fn sample_raw(w: u8) -> impl Fn(&mut ChaCha12Rng) -> u32 {
+    move |rng| rng.gen_range(0..(1u32 << w))
+}
+
+#[test]
+#[ignore]
+fn timing_mul() {
+    for w in [8, 16, 30] {
+        for fixed in [0, 1] {
+            let t = measure(fixed, sample_raw(w), |x, y| ct::mul(x, y, w));
+            assert!(t.abs() < T_THRESHOLD, "mul leaks for W = {w}: t = {t}");
+        }
+    }
+}
+
+#[test]
+#[ignore]
+fn timing_div() {
+    for w in [8, 16] {
+        for fixed in [0, 1] {
+            let t = measure(fixed, sample_raw(w), |x, y| ct::div(x, y, w));
+            assert!(t.abs() < T_THRESHOLD, "div leaks for W = {w}: t = {t}");
+        }
+    }
+}
+
+/// Times the operators of `GF<W>`, which go through carry-less multiplication when the CPU
+/// supports it and through the constant-time backend otherwise.
+#[cfg(feature = "constant-time")]
+fn check_field_timing<const W: u8>() {
+    use pss::math::galois::GF;
+
+    let sample = |rng: &mut ChaCha12Rng| GF::<W>::rand(rng);
+    let nonzero = |rng: &mut ChaCha12Rng| loop {
+        let v = GF::<W>::rand(rng);
+        if v != GF::ZERO {
+            break v;
+        }
+    };
+
+    for fixed in [GF::ZERO, GF::ONE] {
+        let t = measure(fixed, sample, |x, y| x * y);
+        assert!(t.abs() < T_THRESHOLD, "* leaks for W = {W}: t = {t}");
+
+        let t = measure(fixed, sample, |x, y| x.checked_div(y).unwrap_or(GF::ZERO));
+        assert!(
+            t.abs() < T_THRESHOLD,
+            "checked_div leaks for W = {W}: t = {t}"
+        );
+    }
+
+    // The operator panics on zero divisors, so only non-zero ones are compared.
+    let t = measure(GF::ONE, nonzero, |x, y| x / y);
+    assert!(t.abs() < T_THRESHOLD, "/ leaks for W = {W}: t = {t}");
+}
+
+#[cfg(feature = "constant-time")]
+#[test]
+#[ignore]
+fn timing_field_ops() {
+    check_field_timing::<8>();
+    check_field_timing::<16>();
+    check_field_timing::<30>();
+}
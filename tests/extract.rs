@@ -0,0 +1,107 @@
+//! Checks randomness extraction with super-invertible and hyper-invertible matrices.
+use ndarray::{Array1, Array2};
+use pss::math::extract::{extract_randomness, extract_randomness_checked, verify_checks};
+use pss::math::galois::GF;
+use pss::math::linalg::{check_hyper_invertible, MinorCheck};
+use pss::math::ring::GR;
+use pss::math::{hyper_inv_matrix, super_inv_matrix};
+use pss::sharing::PackedSharing;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+type F = GF<16>;
+
+// Each party deals a packed sharing of random secrets. Returns the secrets and the shares with
+// one row per dealer and one column per receiving party.
+fn deal(pss: &PackedSharing<F>, rng: &mut ChaCha12Rng) -> (Array2<F>, Array2<F>) {
+    let (n, l) = (pss.num_parties() as usize, pss.num_secrets() as usize);
+    let mut secrets = Array2::from_elem((n, l), F::ZERO);
+    let mut shares = Array2::from_elem((n, n), F::ZERO);
+
+    for i in 0..n {
+        let s = Array1::from_shape_simple_fn(l, || F::rand(rng));
+        shares
+            .row_mut(i)
+            .assign(&Array1::from(pss.share(s.view(), rng)));
+        secrets.row_mut(i).assign(&s);
+    }
+
+    (secrets, shares)
+}
+
+#[test]
+fn super_invertible_extraction() {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    let (n, t, l) = (10, 3, 2);
+    let pss = PackedSharing::<F>::new(
+        (t + l - 1) as u32,
+        n as u32,
+        &PackedSharing::default_pos(n as u32, l as u32),
+    );
+
+    let (secrets, shares) = deal(&pss, &mut rng);
+    let mat = super_inv_matrix::<F>(n, n - t);
+    let out = extract_randomness(shares.view(), mat.view());
+    assert_eq!(out.dim(), (n - t, n));
+    assert_eq!(out, mat.dot(&shares));
+
+    // Every output is a valid sharing of the same combination of the secrets.
+    let expected = extract_randomness(secrets.view(), mat.view());
+    for (row, exp) in out.rows().into_iter().zip(expected.rows()) {
+        assert_eq!(pss.recon(row).unwrap(), exp.to_vec());
+    }
+}
+
+#[test]
+fn hyper_invertible_extraction() {
+    let mut rng = ChaCha12Rng::seed_from_u64(1);
+    let (n, t, l) = (10, 3, 2);
+    let pss = PackedSharing::<F>::new(
+        (t + l - 1) as u32,
+        n as u32,
+        &PackedSharing::default_pos(n as u32, l as u32),
+    );
+
+    let mat = hyper_inv_matrix::<F>(n, n).unwrap();
+    assert_eq!(
+        check_hyper_invertible(
+            mat.view(),
+            MinorCheck::Randomized {
+                trials: 200,
+                seed: 0
+            }
+        ),
+        Ok(())
+    );
+
+    let (_, mut shares) = deal(&pss, &mut rng);
+    let (out, checks) = extract_randomness_checked(shares.view(), mat.view(), 2 * t);
+    assert_eq!(out.nrows(), n - 2 * t);
+    assert!(verify_checks(&pss, checks.view()).is_ok());
+
+    // An invalid contribution shows up in every check output.
+    shares[[0, 4]] += F::ONE;
+    let (_, checks) = extract_randomness_checked(shares.view(), mat.view(), 2 * t);
+    for row in checks.rows() {
+        assert!(pss.recon(row).is_err());
+    }
+    assert!(verify_checks(&pss, checks.view()).is_err());
+}
+
+#[test]
+fn small_hyper_invertible_matrices() {
+    for (inp, out) in [(1, 1), (4, 4), (6, 3), (3, 6)] {
+        let mat = hyper_inv_matrix::<GF<8>>(inp, out).unwrap();
+        assert_eq!(mat.dim(), (out, inp));
+        assert_eq!(
+            check_hyper_invertible(mat.view(), MinorCheck::Exact),
+            Ok(())
+        );
+    }
+
+    // Positions collide once they run out.
+    assert!(hyper_inv_matrix::<GF<4>>(8, 8).is_ok());
+    assert!(hyper_inv_matrix::<GF<4>>(9, 8).is_err());
+    assert!(hyper_inv_matrix::<GR<16, 2>>(2, 2).is_ok());
+    assert!(hyper_inv_matrix::<GR<16, 2>>(3, 2).is_err());
+}
@@ -0,0 +1,105 @@
+//! Checks that the field arithmetic of `GF<W>` agrees with a straightforward polynomial
+//! multiplication for every supported width.
+//!
+//! When a feature selects another backend, `GF<W>` is also compared with the pure-Rust backend.
+use pss::math::galois::{native, GF};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+/// Whether `GF<W>` uses a backend other than [`native`].
+const OTHER_BACKEND: bool = cfg!(any(feature = "jerasure", feature = "constant-time"));
+
+/// Schoolbook multiplication in GF(2)[x] followed by reduction.
+fn reference_mul(x: u32, y: u32, w: u8) -> u32 {
+    let mut prod = 0u64;
+    for i in 0..w {
+        if (y >> i) & 1 == 1 {
+            prod ^= (x as u64) << i;
+        }
+    }
+
+    let poly = native::PRIM_POLY[w as usize] as u64;
+    for i in (w..(2 * w)).rev() {
+        if (prod >> i) & 1 == 1 {
+            prod ^= poly << (i - w);
+        }
+    }
+
+    prod as u32
+}
+
+fn check_width<const W: u8>() {
+    let order = GF::<W>::ORDER;
+    let pairs: Vec<(u32, u32)> = if W <= 8 {
+        (0..order)
+            .flat_map(|x| (0..order).map(move |y| (x, y)))
+            .collect()
+    } else {
+        let mut rng = ChaCha12Rng::seed_from_u64(W.into());
+        (0..100_000)
+            .map(|_| (rng.gen_range(0..order), rng.gen_range(0..order)))
+            .collect()
+    };
+
+    for (x, y) in pairs {
+        let prod: u32 = (GF::<W>::from(x) * GF::<W>::from(y)).into();
+        assert_eq!(prod, reference_mul(x, y, W), "mul {x} {y} for W = {W}");
+        if OTHER_BACKEND {
+            assert_eq!(prod, native::mul(x, y, W), "mul {x} {y} for W = {W}");
+        }
+
+        if y != 0 {
+            let quot: u32 = (GF::<W>::from(x) / GF::<W>::from(y)).into();
+            assert_eq!(reference_mul(quot, y, W), x, "div {x} {y} for W = {W}");
+            if OTHER_BACKEND {
+                assert_eq!(quot, native::div(x, y, W), "div {x} {y} for W = {W}");
+            }
+        }
+    }
+}
+
+#[test]
+fn small_widths() {
+    check_width::<1>();
+    check_width::<2>();
+    check_width::<3>();
+    check_width::<4>();
+    check_width::<5>();
+    check_width::<6>();
+    check_width::<7>();
+    check_width::<8>();
+    check_width::<9>();
+    check_width::<10>();
+    check_width::<11>();
+    check_width::<12>();
+    check_width::<13>();
+    check_width::<14>();
+    check_width::<15>();
+    check_width::<16>();
+}
+
+#[test]
+fn medium_widths() {
+    check_width::<17>();
+    check_width::<18>();
+    check_width::<19>();
+    check_width::<20>();
+    check_width::<21>();
+    check_width::<22>();
+    check_width::<23>();
+}
+
+#[test]
+#[cfg_attr(
+    all(feature = "jerasure", not(feature = "constant-time")),
+    ignore = "the C log tables for these widths take several gigabytes of memory"
+)]
+fn large_widths() {
+    check_width::<24>();
+    check_width::<25>();
+    check_width::<26>();
+    check_width::<27>();
+    check_width::<28>();
+    check_width::<29>();
+    check_width::<30>();
+}
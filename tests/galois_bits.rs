@@ -0,0 +1,66 @@
+//! Checks conversions of `GF<W>` to bits and bytes and its binary multiplication matrices.
+use ndarray::Array1;
+use pss::math::galois::bits::{from_bit_vec, to_bit_vec};
+use pss::math::galois::GF;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+fn as_gf2<const W: u8>(v: GF<W>) -> Array1<GF<1>> {
+    (0..(W as usize))
+        .map(|i| GF::from(u32::from(v.bit(i))))
+        .collect()
+}
+
+fn check_width<const W: u8, const N: usize>() {
+    let mut rng = ChaCha12Rng::seed_from_u64(W.into());
+
+    assert!(GF::<W>::try_from_u32(GF::<W>::ORDER).is_err());
+    assert_eq!(
+        GF::<W>::try_from_u32(GF::<W>::ORDER - 1),
+        Ok(GF::from(GF::<W>::ORDER - 1))
+    );
+
+    let frob = GF::<W>::frobenius_matrix();
+    assert_eq!(frob.dim(), (W.into(), W.into()));
+
+    let vals: Vec<GF<W>> = (0..50).map(|_| GF::rand(&mut rng)).collect();
+    for (&c, &x) in vals.iter().zip(vals.iter().rev()) {
+        let bits: [bool; N] = x.to_bits();
+        assert_eq!(GF::<W>::from_bits(bits), x);
+        assert_eq!(GF::<W>::from_bit_slice(&bits), Ok(x));
+        assert_eq!(
+            u32::from(x),
+            bits.iter()
+                .rev()
+                .fold(0, |acc, &b| (acc << 1) | u32::from(b))
+        );
+
+        assert_eq!(GF::<W>::from_bytes(&x.to_bytes()), Ok(x));
+        assert_eq!(x.to_bytes(), bincode::serialize(&x).unwrap());
+
+        assert_eq!(c.mul_matrix().mul_vec(as_gf2(x).view()), as_gf2(c * x));
+        assert_eq!(frob.mul_vec(as_gf2(x).view()), as_gf2(x * x));
+    }
+
+    assert_eq!(from_bit_vec::<W>(&to_bit_vec(&vals)).unwrap(), vals);
+    assert_eq!(to_bit_vec(&vals).len(), 50 * W as usize);
+    assert!(GF::<W>::from_bit_slice(&[false; 31]).is_err());
+}
+
+#[test]
+fn conversions() {
+    check_width::<1, 1>();
+    check_width::<3, 3>();
+    check_width::<8, 8>();
+    check_width::<9, 9>();
+    check_width::<16, 16>();
+    check_width::<30, 30>();
+}
+
+#[test]
+fn invalid_inputs() {
+    assert!(GF::<9>::from_bytes(&600u16.to_le_bytes()).is_err());
+    assert!(GF::<9>::from_bytes(&[1]).is_err());
+    assert!(GF::<9>::from_bytes(&[1, 0, 0]).is_err());
+    assert!(from_bit_vec::<9>(&[true; 10]).is_err());
+}
@@ -0,0 +1,112 @@
+//! Checks the batched kernels against scalar loops of field operations.
+//!
+//! Widths up to 16 exercise the log tables (through `Scaler` in `scale`, `axpy` and `mat_mul`),
+//! while larger widths exercise carry-less multiplication with delayed reduction when the CPU
+//! supports it. Products in the scalar loops use the pure-Rust backend so that they don't share
+//! the code path under test.
+use ndarray::{Array1, Array2};
+use pss::math::galois::{self, clmul, native, GF};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+fn mul<const W: u8>(x: GF<W>, y: GF<W>) -> GF<W> {
+    GF::from(native::mul(x.into(), y.into(), W))
+}
+
+/// Random elements, a fifth of which are zero and a fifth of which are the largest element.
+fn sample<const W: u8, R: Rng>(len: usize, rng: &mut R) -> Vec<GF<W>> {
+    (0..len)
+        .map(|_| match rng.gen_range(0..5) {
+            0 => GF::ZERO,
+            1 => GF::from(GF::<W>::ORDER - 1),
+            _ => GF::rand(rng),
+        })
+        .collect()
+}
+
+fn check_width<const W: u8>() {
+    native::init(W).unwrap();
+    let mut rng = ChaCha12Rng::seed_from_u64(W.into());
+
+    for len in [0, 1, 7, 64, 1000] {
+        let a = sample::<W, _>(len, &mut rng);
+        let b = sample::<W, _>(len, &mut rng);
+
+        let mut out = vec![GF::ZERO; len];
+        galois::mul_slice(&mut out, &a, &b);
+        for i in 0..len {
+            assert_eq!(out[i], mul(a[i], b[i]), "mul_slice for W = {W}");
+        }
+
+        let expected = (0..len).fold(GF::ZERO, |acc, i| acc + mul(a[i], b[i]));
+        assert_eq!(galois::dot(&a, &b), expected, "dot for W = {W}");
+
+        for c in [GF::ZERO, GF::ONE, GF::rand(&mut rng)] {
+            let mut x = a.clone();
+            galois::scale(c, &mut x);
+            for i in 0..len {
+                assert_eq!(x[i], mul(c, a[i]), "scale for W = {W}");
+            }
+
+            let mut y = b.clone();
+            galois::axpy(c, &a, &mut y);
+            for i in 0..len {
+                assert_eq!(y[i], b[i] + mul(c, a[i]), "axpy for W = {W}");
+            }
+        }
+    }
+
+    for (rows, inner, cols) in [(1, 1, 1), (5, 9, 3), (33, 17, 40)] {
+        let a =
+            Array2::from_shape_vec((rows, inner), sample::<W, _>(rows * inner, &mut rng)).unwrap();
+        let b =
+            Array2::from_shape_vec((inner, cols), sample::<W, _>(inner * cols, &mut rng)).unwrap();
+        let v = Array1::from_vec(sample::<W, _>(inner, &mut rng));
+
+        let expected = Array1::from_shape_fn(rows, |i| {
+            (0..inner).fold(GF::ZERO, |acc, k| acc + mul(a[[i, k]], v[k]))
+        });
+        assert_eq!(
+            galois::mat_vec(a.view(), v.view()),
+            expected,
+            "mat_vec for W = {W}"
+        );
+
+        let expected = Array2::from_shape_fn((rows, cols), |(i, j)| {
+            (0..inner).fold(GF::ZERO, |acc, k| acc + mul(a[[i, k]], b[[k, j]]))
+        });
+        assert_eq!(
+            galois::mat_mul(a.view(), b.view()),
+            expected,
+            "mat_mul for W = {W}"
+        );
+
+        // Transposed views aren't contiguous, which the kernels shouldn't rely on.
+        let expected = Array2::from_shape_fn((cols, rows), |(j, i)| expected[[i, j]]);
+        assert_eq!(
+            galois::mat_mul(b.t(), a.t()),
+            expected,
+            "transposed mat_mul for W = {W}"
+        );
+    }
+}
+
+#[test]
+fn log_tables() {
+    check_width::<1>();
+    check_width::<4>();
+    check_width::<8>();
+    check_width::<12>();
+    check_width::<16>();
+}
+
+#[test]
+fn carry_less() {
+    if !clmul::is_available() {
+        eprintln!("carry-less multiplication is unavailable, using the backend instead");
+    }
+
+    check_width::<17>();
+    check_width::<24>();
+    check_width::<30>();
+}
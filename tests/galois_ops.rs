@@ -0,0 +1,92 @@
+//! Checks division, exponentiation, Frobenius, trace and minimal polynomials of `GF<W>`.
+use pss::math::galois::GF;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+fn elements<const W: u8>() -> Vec<GF<W>> {
+    if W <= 10 {
+        (0..GF::<W>::ORDER).map(GF::from).collect()
+    } else {
+        let mut rng = ChaCha12Rng::seed_from_u64(W.into());
+        (0..1000).map(|_| GF::rand(&mut rng)).collect()
+    }
+}
+
+fn check_width<const W: u8>() {
+    let order = GF::<W>::ORDER as u64;
+    let mut num_trace_one = 0;
+    for x in elements::<W>() {
+        assert_eq!(x.checked_div(GF::ZERO), None);
+        assert_eq!(x.pow(0), GF::ONE);
+        assert_eq!(x.pow(5), x * x * x * x * x);
+        assert_eq!(x.pow(order), x);
+
+        if x != GF::ZERO {
+            let inv = x.checked_inv().unwrap();
+            assert_eq!(x * inv, GF::ONE);
+            assert_eq!(x.checked_div(x), Some(GF::ONE));
+            assert_eq!(x.pow(order - 1), GF::ONE);
+        }
+
+        assert_eq!(x.square(), x * x);
+        assert_eq!(x.sqrt().square(), x);
+        assert_eq!(x.frobenius(W.into()), x);
+        assert_eq!(x.frobenius(3), x.pow(8));
+
+        let trace = x.trace();
+        assert!(trace == GF::ZERO || trace == GF::ONE);
+        assert_eq!(trace, x.square().trace());
+        if trace == GF::ONE {
+            num_trace_one += 1;
+        }
+
+        let min_poly = x.min_poly();
+        let deg = min_poly.degree().unwrap();
+        assert_eq!(W as usize % deg, 0);
+        assert_eq!(min_poly.leading_coeff(), GF::ONE);
+        assert!(min_poly
+            .coeffs()
+            .iter()
+            .all(|&c| c == GF::ZERO || c == GF::ONE));
+        assert_eq!(min_poly.eval(x), GF::ZERO);
+    }
+
+    // The trace is a non-zero linear map to GF(2), so exactly half the elements have trace one.
+    if W <= 10 {
+        assert_eq!(num_trace_one, GF::<W>::ORDER / 2);
+    }
+
+    // The generator has order exactly 2^W - 1.
+    let g = GF::<W>::generator();
+    assert_eq!(g.pow(order - 1), GF::ONE);
+    if W <= 10 {
+        let mut v = g;
+        for _ in 1..(order - 1) {
+            assert_ne!(v, GF::ONE);
+            v *= g;
+        }
+    }
+}
+
+#[test]
+fn small_widths() {
+    check_width::<1>();
+    check_width::<2>();
+    check_width::<4>();
+    check_width::<7>();
+    check_width::<8>();
+    check_width::<10>();
+}
+
+#[test]
+fn large_widths() {
+    check_width::<16>();
+    check_width::<20>();
+    check_width::<30>();
+}
+
+#[test]
+#[should_panic(expected = "Division by zero.")]
+fn div_by_zero() {
+    let _ = GF::<8>::ONE / GF::ZERO;
+}
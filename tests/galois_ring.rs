@@ -0,0 +1,146 @@
+//! Checks the arithmetic of Galois rings and that packed sharing works over them.
+use ndarray::ArrayView;
+use pss::math::ring::GR;
+use pss::math::{lagrange_coeffs, rs_gen_mat, Field};
+use pss::sharing::PackedSharing;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+type R64 = GR<64, 4>;
+type R32 = GR<32, 5>;
+
+#[test]
+fn integer_arithmetic() {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let (x, y): (u64, u64) = (rng.gen(), rng.gen());
+        let (a, b) = (R64::from_int(x), R64::from_int(y));
+        assert_eq!((a * b).to_int(), x.wrapping_mul(y));
+        assert_eq!((a + b).to_int(), x.wrapping_add(y));
+        assert_eq!((a - b).to_int(), x.wrapping_sub(y));
+
+        let (x, y) = (x as u32, y as u32);
+        let (a, b) = (R32::from_int(x.into()), R32::from_int(y.into()));
+        assert_eq!((a * b).to_int(), u64::from(x.wrapping_mul(y)));
+    }
+}
+
+#[test]
+fn inverse() {
+    let mut rng = ChaCha12Rng::seed_from_u64(1);
+    for _ in 0..1000 {
+        let x = R64::rand(&mut rng);
+        match x.inv() {
+            Some(v) => assert_eq!(x * v, R64::ONE),
+            None => assert!(!x.is_unit()),
+        }
+    }
+    assert_eq!(R64::from_int(2).inv(), None);
+
+    // Differences between elements of the exceptional set are units.
+    for i in 0u32..16 {
+        for j in 0u32..16 {
+            if i != j {
+                assert!((R64::from(i) - R64::from(j)).is_unit());
+            }
+        }
+    }
+}
+
+#[test]
+fn interpolation() {
+    let mut rng = ChaCha12Rng::seed_from_u64(2);
+    let cpos: Vec<R64> = (0u32..4).map(R64::from).collect();
+    let npos: Vec<R64> = (4u32..10).map(R64::from).collect();
+
+    // A polynomial of degree 3 with random coefficients.
+    let coeffs: Vec<_> = (0..4).map(|_| R64::rand(&mut rng)).collect();
+    let eval = |x: R64| coeffs.iter().rev().fold(R64::ZERO, |acc, &c| acc * x + c);
+
+    let vals: Vec<_> = cpos.iter().map(|&x| eval(x)).collect();
+    let interp = R64::mat_vec(lagrange_coeffs(&cpos, &npos).view(), ArrayView::from(&vals));
+    for (&x, &v) in npos.iter().zip(interp.iter()) {
+        assert_eq!(eval(x), v);
+    }
+
+    let gen = rs_gen_mat::<R64>(4, 10);
+    assert_eq!(gen.shape(), &[10, 4]);
+    assert_eq!(gen[[2, 1]], R64::from(3u32) * R64::from(3u32));
+}
+
+#[test]
+fn share_and_recon() {
+    let mut rng = ChaCha12Rng::seed_from_u64(3);
+    let (n, l, d) = (10, 3, 4);
+
+    let pos = PackedSharing::default_pos(n, l);
+    let pss = PackedSharing::<R64>::new(d, n, &pos);
+    let pss2 = PackedSharing::<R64>::new(2 * d, n, &pos);
+
+    let x: Vec<u64> = (0..l).map(|_| rng.gen()).collect();
+    let y: Vec<u64> = (0..l).map(|_| rng.gen()).collect();
+    let x_secrets: Vec<_> = x.iter().map(|&v| R64::from_int(v)).collect();
+    let y_secrets: Vec<_> = y.iter().map(|&v| R64::from_int(v)).collect();
+
+    let x_shares = pss.share(ArrayView::from(&x_secrets), &mut rng);
+    let y_shares = pss.share(ArrayView::from(&y_secrets), &mut rng);
+    assert_eq!(pss.recon(ArrayView::from(&x_shares)).unwrap(), x_secrets);
+
+    let xy_shares: Vec<_> = x_shares
+        .iter()
+        .zip(y_shares.iter())
+        .map(|(&a, &b)| a * b)
+        .collect();
+    let xy: Vec<_> = pss2
+        .semihon_recon(ArrayView::from(&xy_shares))
+        .iter()
+        .map(|v| v.to_int())
+        .collect();
+    let expected: Vec<_> = x
+        .iter()
+        .zip(y.iter())
+        .map(|(a, b)| a.wrapping_mul(*b))
+        .collect();
+    assert_eq!(xy, expected);
+}
+
+#[test]
+#[should_panic(expected = "Positions should be distinct")]
+fn too_many_positions() {
+    // The exceptional set of GR(2^64, 4) has 16 elements, so position 16 wraps around to 0.
+    PackedSharing::<R64>::new(4, 10, &PackedSharing::default_pos(10, 7));
+}
+
+#[test]
+fn large_degree() {
+    // The product is reduced using a stack buffer sized for the largest supported degree.
+    type R = GR<16, 30>;
+    let mut rng = ChaCha12Rng::seed_from_u64(4);
+    for _ in 0..100 {
+        let (x, y) = (R::rand(&mut rng), R::rand(&mut rng));
+        if let Some(v) = y.inv() {
+            assert_eq!(x * y * v, x);
+        }
+        assert_eq!(x * (y + R::ONE), x * y + x);
+    }
+}
+
+#[test]
+fn serialization() {
+    type R = GR<12, 3>;
+    let mut rng = ChaCha12Rng::seed_from_u64(5);
+    let x = R::rand(&mut rng);
+    let bytes = bincode::serialize(&x).unwrap();
+    assert_eq!(bytes.len(), R::NUM_BYTES);
+    assert_eq!(bincode::deserialize::<R>(&bytes).unwrap(), x);
+
+    // Truncated input.
+    assert!(bincode::deserialize::<R>(&bytes[..R::NUM_BYTES - 1]).is_err());
+
+    // The second coefficient is 2^12, which is outside Z/2^12.
+    let mut bytes = bytes;
+    bytes[2..4].copy_from_slice(&(1u16 << 12).to_le_bytes());
+    assert!(bincode::deserialize::<R>(&bytes).is_err());
+    bytes[2..4].copy_from_slice(&((1u16 << 12) - 1).to_le_bytes());
+    assert!(bincode::deserialize::<R>(&bytes).is_ok());
+}
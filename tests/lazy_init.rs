@@ -0,0 +1,47 @@
+//! Checks that field arithmetic works without calling `GF::init`, including for several widths
+//! used concurrently.
+use pss::math::galois::{native, GF};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use std::thread;
+
+fn check_width<const W: u8>(seed: u64) {
+    let mut rng = ChaCha12Rng::seed_from_u64(seed);
+    for _ in 0..10_000 {
+        let x = rng.gen_range(0..GF::<W>::ORDER);
+        let y = rng.gen_range(1..GF::<W>::ORDER);
+
+        let (gx, gy) = (GF::<W>::from(x), GF::<W>::from(y));
+        assert_eq!(u32::from(gx * gy), native::mul(x, y, W));
+        assert_eq!(u32::from(gx / gy), native::div(x, y, W));
+    }
+}
+
+#[test]
+fn mixed_widths_without_init() {
+    let handles: Vec<_> = (0..4)
+        .flat_map(|seed| {
+            [
+                thread::spawn(move || check_width::<8>(seed)),
+                thread::spawn(move || check_width::<16>(seed)),
+                thread::spawn(move || check_width::<11>(seed)),
+            ]
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn unsupported_widths() {
+    assert!(GF::<0>::init().is_err());
+    assert!(GF::<31>::init().is_err());
+    assert!(GF::<30>::init().is_ok());
+
+    // Initializing again, or after another width, still succeeds.
+    assert!(GF::<8>::init().is_ok());
+    assert!(GF::<16>::init().is_ok());
+    assert!(GF::<8>::init().is_ok());
+}
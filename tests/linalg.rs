@@ -0,0 +1,147 @@
+//! Checks Gaussian elimination over binary and prime fields.
+mod common;
+
+use common::rand_mat;
+use ndarray::{Array1, Array2};
+use pss::math::galois::GF;
+use pss::math::linalg::{
+    check_hyper_invertible, check_super_invertible, determinant, inverse, kernel, rank, solve,
+    Minor, MinorCheck,
+};
+use pss::math::prime::Mersenne61;
+use pss::math::{rs_gen_mat, super_inv_matrix, Field};
+use rand::Rng;
+use rand_chacha::ChaCha12Rng;
+
+fn check_elimination<F: Field>(rng: &mut ChaCha12Rng) {
+    for _ in 0..20 {
+        let n = rng.gen_range(1..12);
+        let a = rand_mat::<F, _>(n, n, rng);
+        let b = rand_mat::<F, _>(n, n, rng);
+
+        // Random matrices over large fields are invertible with high probability.
+        let inv = inverse(a.view()).unwrap();
+        assert_eq!(a.dot(&inv), Array2::eye(n));
+        assert_eq!(rank(a.view()), n);
+        assert_eq!(
+            determinant(a.dot(&b).view()),
+            determinant(a.view()) * determinant(b.view())
+        );
+        assert_eq!(
+            determinant(inv.view()),
+            determinant(a.view()).inv().unwrap()
+        );
+
+        // Systems with an invertible matrix have a unique solution.
+        let x = Array1::from_shape_simple_fn(n, || F::rand(rng));
+        assert_eq!(solve(a.view(), a.dot(&x).view()).unwrap(), x);
+        assert_eq!(kernel(a.view()).nrows(), 0);
+
+        // Product through a narrower matrix has lower rank.
+        let k = rng.gen_range(0..n);
+        let low = rand_mat::<F, _>(n, k, rng).dot(&rand_mat::<F, _>(k, n + 3, rng));
+        assert_eq!(rank(low.view()), k);
+
+        let ker = kernel(low.view());
+        assert_eq!(ker.nrows(), n + 3 - k);
+        assert_eq!(rank(ker.view()), n + 3 - k);
+        assert!(low.dot(&ker.t()).iter().all(|v| v.is_zero()));
+
+        let square = low.slice(ndarray::s![.., ..n]).to_owned();
+        assert!(inverse(square.view()).is_none());
+        assert_eq!(determinant(square.view()), F::ZERO);
+
+        // Consistent and inconsistent systems.
+        let x = Array1::from_shape_simple_fn(n + 3, || F::rand(rng));
+        let y = low.dot(&x);
+        let sol = solve(low.view(), y.view()).unwrap();
+        assert_eq!(low.dot(&sol), y);
+
+        if k < n {
+            let y = Array1::from_shape_simple_fn(n, || F::rand(rng));
+            assert!(solve(low.view(), y.view()).is_none());
+        }
+    }
+}
+
+common::field_tests!(check_elimination);
+
+#[test]
+fn vandermonde_is_invertible() {
+    let mat = rs_gen_mat::<GF<8>>(30, 30);
+    assert_eq!(rank(mat.view()), 30);
+    assert_ne!(determinant(mat.view()), GF::ZERO);
+}
+
+#[test]
+fn super_invertibility() {
+    let mat = super_inv_matrix::<GF<8>>(12, 5);
+    assert_eq!(
+        check_super_invertible(mat.view(), MinorCheck::Exact),
+        Ok(())
+    );
+    assert_eq!(
+        check_super_invertible(
+            mat.t(),
+            MinorCheck::Randomized {
+                trials: 50,
+                seed: 0
+            }
+        ),
+        Ok(())
+    );
+
+    let mat = super_inv_matrix::<Mersenne61>(200, 100);
+    let mode = MinorCheck::Randomized {
+        trials: 20,
+        seed: 1,
+    };
+    assert_eq!(check_super_invertible(mat.view(), mode), Ok(()));
+
+    // Repeating a column makes every subset containing both copies singular.
+    let mut mat = super_inv_matrix::<GF<8>>(6, 3);
+    let col = mat.column(1).to_owned();
+    mat.column_mut(4).assign(&col);
+    assert_eq!(
+        check_super_invertible(mat.view(), MinorCheck::Exact),
+        Err(Minor {
+            rows: vec![0, 1, 2],
+            cols: vec![0, 1, 4]
+        })
+    );
+}
+
+#[test]
+fn hyper_invertibility() {
+    // Every square submatrix of a Cauchy matrix is a Cauchy matrix, which is invertible.
+    let cauchy = Array2::from_shape_fn((5, 7), |(i, j)| {
+        GF::<8>::ONE / (GF::from(i as u32) - GF::from(100 + j as u32))
+    });
+    assert_eq!(
+        check_hyper_invertible(cauchy.view(), MinorCheck::Exact),
+        Ok(())
+    );
+    let mode = MinorCheck::Randomized {
+        trials: 100,
+        seed: 0,
+    };
+    assert_eq!(check_hyper_invertible(cauchy.view(), mode), Ok(()));
+
+    // A zero entry is a singular 1x1 submatrix, which is checked first.
+    let mut mat = cauchy.clone();
+    mat[[3, 2]] = GF::ZERO;
+    assert_eq!(
+        check_hyper_invertible(mat.view(), MinorCheck::Exact),
+        Err(Minor {
+            rows: vec![3],
+            cols: vec![2]
+        })
+    );
+
+    let mut mat = cauchy;
+    let row = mat.row(0).to_owned();
+    mat.row_mut(1)
+        .zip_mut_with(&row, |v, &r| *v = r * GF::from(2u32));
+    assert!(check_hyper_invertible(mat.view(), MinorCheck::Exact).is_err());
+    assert!(check_super_invertible(mat.view(), MinorCheck::Exact).is_err());
+}
@@ -0,0 +1,182 @@
+//! Checks storing and loading pre-computed matrices.
+use ndarray::Array2;
+use pss::math::galois::GF;
+use pss::math::matrix_file::{
+    load_matrix, read_matrix, save_matrix, write_matrix, FieldId, MatrixFileError, MatrixKind,
+};
+use pss::math::prime::Mersenne61;
+use pss::math::ring::GR;
+use pss::math::{binary_super_inv_matrix, lagrange_coeffs, rs_gen_mat, Field};
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("pss_{}_{}", name, std::process::id()))
+}
+
+#[test]
+fn roundtrip() {
+    let pos: Vec<GF<16>> = (0..20u32).map(GF::from).collect();
+    let coeffs = lagrange_coeffs(&pos[..8], &pos[8..]);
+    let mut buf = Vec::new();
+    write_matrix(&mut buf, coeffs.view(), MatrixKind::Lagrange).unwrap();
+    assert_eq!(buf.len(), 64 + coeffs.len() * 2);
+    let read: Array2<GF<16>> = read_matrix(&mut buf.as_slice(), MatrixKind::Lagrange).unwrap();
+    assert_eq!(read, coeffs);
+
+    let gen = rs_gen_mat::<Mersenne61>(10, 30);
+    let path = temp_path("rs_gen.bin");
+    save_matrix(&path, gen.view(), MatrixKind::RsGenerator).unwrap();
+    let read = load_matrix::<Mersenne61>(&path, MatrixKind::RsGenerator);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(read.unwrap(), gen);
+}
+
+#[test]
+fn invalid_container() {
+    GF::<16>::init().unwrap();
+
+    let gen = rs_gen_mat::<GF<16>>(4, 6);
+    let mut buf = Vec::new();
+    write_matrix(&mut buf, gen.view(), MatrixKind::RsGenerator).unwrap();
+
+    let read = |bytes: &[u8], kind| read_matrix::<GF<16>, _>(&mut &bytes[..], kind);
+    assert!(read(&buf, MatrixKind::RsGenerator).is_ok());
+    assert!(matches!(
+        read(&buf, MatrixKind::SuperInvertible),
+        Err(MatrixFileError::KindMismatch { .. })
+    ));
+    assert!(matches!(
+        read_matrix::<GF<8>, _>(&mut buf.as_slice(), MatrixKind::RsGenerator),
+        Err(MatrixFileError::FieldMismatch { expected, found })
+            if expected == FieldId::of::<GF<8>>() && found == FieldId::of::<GF<16>>()
+    ));
+
+    let mut corrupt = buf.clone();
+    *corrupt.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        read(&corrupt, MatrixKind::RsGenerator),
+        Err(MatrixFileError::ChecksumMismatch)
+    ));
+
+    assert!(matches!(
+        read(&buf[..(buf.len() - 1)], MatrixKind::RsGenerator),
+        Err(MatrixFileError::Malformed(_))
+    ));
+    assert!(matches!(
+        read(&buf[..10], MatrixKind::RsGenerator),
+        Err(MatrixFileError::Io(_))
+    ));
+
+    let mut bad = buf.clone();
+    bad[0] = b'X';
+    assert!(matches!(
+        read(&bad, MatrixKind::RsGenerator),
+        Err(MatrixFileError::BadMagic)
+    ));
+
+    let mut bad = buf;
+    bad[4] = 2;
+    assert!(matches!(
+        read(&bad, MatrixKind::RsGenerator),
+        Err(MatrixFileError::UnsupportedVersion(2))
+    ));
+}
+
+/// Writes `matrix` and reads it back as a matrix over `G`.
+fn reinterpret<F: Field, G: Field>(matrix: &Array2<F>) -> Result<Array2<G>, MatrixFileError> {
+    let mut buf = Vec::new();
+    write_matrix(&mut buf, matrix.view(), MatrixKind::Generic).unwrap();
+    read_matrix(&mut buf.as_slice(), MatrixKind::Generic)
+}
+
+#[test]
+fn field_identity() {
+    // Same width and bytes per entry, but different fields and rings.
+    let gf = Array2::from_shape_fn((3, 4), |(i, j)| GF::<8>::from((i * 4 + j) as u32));
+    assert!(reinterpret::<_, GF<8>>(&gf).is_ok());
+    assert!(matches!(
+        reinterpret::<_, GR<8, 1>>(&gf),
+        Err(MatrixFileError::FieldMismatch { .. })
+    ));
+
+    let gr = Array2::from_shape_fn((2, 2), |(i, j)| GR::<64, 4>::from((i * 2 + j) as u32));
+    assert!(reinterpret::<_, GR<64, 4>>(&gr).is_ok());
+    assert!(matches!(
+        reinterpret::<_, GR<32, 8>>(&gr),
+        Err(MatrixFileError::FieldMismatch { .. })
+    ));
+}
+
+/// Overwrites the first entry of a stored matrix and fixes up the checksum.
+fn with_first_entry(mut buf: Vec<u8>, entry: &[u8]) -> Vec<u8> {
+    buf[64..(64 + entry.len())].copy_from_slice(entry);
+    let checksum = seahash::hash(&buf[64..]);
+    buf[56..64].copy_from_slice(&checksum.to_le_bytes());
+    buf
+}
+
+#[test]
+fn out_of_range_entries() {
+    let mut buf = Vec::new();
+    let gf = Array2::from_elem((2, 3), GF::<12>::ONE);
+    write_matrix(&mut buf, gf.view(), MatrixKind::Generic).unwrap();
+    let bad = with_first_entry(buf, &0x1000u16.to_le_bytes());
+    assert!(matches!(
+        read_matrix::<GF<12>, _>(&mut bad.as_slice(), MatrixKind::Generic),
+        Err(MatrixFileError::Malformed(_))
+    ));
+
+    let mut buf = Vec::new();
+    let prime = Array2::from_elem((2, 3), Mersenne61::ONE);
+    write_matrix(&mut buf, prime.view(), MatrixKind::Generic).unwrap();
+    let ok = with_first_entry(buf.clone(), &(Mersenne61::MODULUS - 1).to_le_bytes());
+    assert!(read_matrix::<Mersenne61, _>(&mut ok.as_slice(), MatrixKind::Generic).is_ok());
+    let bad = with_first_entry(buf, &Mersenne61::MODULUS.to_le_bytes());
+    assert!(matches!(
+        read_matrix::<Mersenne61, _>(&mut bad.as_slice(), MatrixKind::Generic),
+        Err(MatrixFileError::Malformed(_))
+    ));
+
+    // Coefficients are serialized in whole bytes, so the top bits must be zero.
+    let mut buf = Vec::new();
+    let ring = Array2::from_elem((2, 3), GR::<12, 2>::ONE);
+    write_matrix(&mut buf, ring.view(), MatrixKind::Generic).unwrap();
+    let bad = with_first_entry(buf, &0x1001u16.to_le_bytes());
+    assert!(matches!(
+        read_matrix::<GR<12, 2>, _>(&mut bad.as_slice(), MatrixKind::Generic),
+        Err(MatrixFileError::Malformed(_))
+    ));
+}
+
+#[test]
+fn invalid_text_matrix() {
+    let load = |contents: &str| {
+        let path = temp_path("text_matrix.txt");
+        std::fs::write(&path, contents).unwrap();
+        let res = binary_super_inv_matrix::<Mersenne61>(&path);
+        std::fs::remove_file(&path).unwrap();
+        res
+    };
+
+    let mat = load("1 0 1\n0 1 1\n\n").unwrap();
+    assert_eq!(mat.dim(), (3, 2));
+    assert_eq!(mat[[2, 0]], Mersenne61::from(1u32));
+
+    assert!(matches!(
+        load("1 0 1\n0 2 1\n"),
+        Err(MatrixFileError::InvalidEntry { line: 2, ref entry }) if entry == "2"
+    ));
+    assert!(matches!(
+        load("1 0 1\n0 1\n"),
+        Err(MatrixFileError::RaggedRow {
+            line: 2,
+            expected: 3,
+            found: 2
+        })
+    ));
+    assert!(matches!(load("\n"), Err(MatrixFileError::Empty)));
+    assert!(matches!(
+        binary_super_inv_matrix::<Mersenne61>(&temp_path("missing")),
+        Err(MatrixFileError::Io(_))
+    ));
+}
@@ -0,0 +1,71 @@
+//! Checks bit-packed serialization of vectors and matrices over GF(2^W).
+use ndarray::{Array1, Array2};
+use pss::math::galois::packed::{pack, packed_len, unpack, PackedMatrix, PackedVec};
+use pss::math::galois::GF;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+fn check_width<const W: u8>() {
+    let mut rng = ChaCha12Rng::seed_from_u64(W.into());
+
+    for n in [0, 1, 7, 8, 9, 100] {
+        let vals: Vec<GF<W>> = (0..n).map(|_| GF::rand(&mut rng)).collect();
+        let bytes = pack(&vals);
+        assert_eq!(bytes.len(), packed_len::<W>(n));
+        assert_eq!(bytes.len(), (n * W as usize).div_ceil(8));
+        assert_eq!(unpack::<W>(&bytes, n).unwrap(), vals);
+
+        let packed = PackedVec::from(Array1::from_vec(vals.clone()));
+        let wire = bincode::serialize(&packed).unwrap();
+        assert_eq!(wire.len(), 16 + packed_len::<W>(n));
+        let restored: PackedVec<W> = bincode::deserialize(&wire).unwrap();
+        assert_eq!(Array1::from(restored), Array1::from_vec(vals));
+    }
+
+    let mat = Array2::from_shape_simple_fn((13, 7), || GF::<W>::rand(&mut rng));
+    let wire = bincode::serialize(&PackedMatrix::from(mat.clone())).unwrap();
+    assert_eq!(wire.len(), 24 + packed_len::<W>(mat.len()));
+    let restored: PackedMatrix<W> = bincode::deserialize(&wire).unwrap();
+    assert_eq!(Array2::from(restored), mat);
+}
+
+#[test]
+fn roundtrip() {
+    check_width::<1>();
+    check_width::<3>();
+    check_width::<8>();
+    check_width::<9>();
+    check_width::<16>();
+    check_width::<30>();
+}
+
+#[test]
+fn invalid_encodings() {
+    let vals = vec![GF::<3>::from(5u32), GF::from(7u32), GF::from(1u32)];
+    let mut bytes = pack(&vals);
+    assert_eq!(bytes.len(), 2);
+
+    assert!(unpack::<3>(&bytes, 2).is_err());
+    assert!(unpack::<3>(&bytes[..1], 3).is_err());
+    assert!(unpack::<3>(&bytes, usize::MAX).is_err());
+
+    // The last 7 bits are padding.
+    bytes[1] |= 0x80;
+    assert!(unpack::<3>(&bytes, 3).is_err());
+
+    let wire = bincode::serialize(&(u64::MAX, vec![0u8; 4])).unwrap();
+    assert!(bincode::deserialize::<PackedVec<3>>(&wire).is_err());
+    let wire = bincode::serialize(&(u64::MAX, 2u64, vec![0u8; 4])).unwrap();
+    assert!(bincode::deserialize::<PackedMatrix<3>>(&wire).is_err());
+}
+
+#[test]
+fn whole_byte_encoding_rejects_large_values() {
+    let v = GF::<9>::from(300u32);
+    let bytes = bincode::serialize(&v).unwrap();
+    assert_eq!(bincode::deserialize::<GF<9>>(&bytes).unwrap(), v);
+
+    let too_large = 600u16.to_le_bytes();
+    assert!(bincode::deserialize::<GF<9>>(&too_large).is_err());
+    assert!(bincode::deserialize::<GF<9>>(&too_large[..1]).is_err());
+}
@@ -0,0 +1,83 @@
+//! Checks polynomial arithmetic and that it agrees with packed sharing.
+mod common;
+
+use ndarray::ArrayView;
+use pss::math::galois::GF;
+use pss::math::poly::Poly;
+use pss::math::prime::Mersenne61;
+use pss::math::Field;
+use pss::sharing::PackedSharing;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+fn rand_poly<F: Field, R: Rng>(deg: usize, rng: &mut R) -> Poly<F> {
+    Poly::new((0..=deg).map(|_| F::rand(rng)).collect())
+}
+
+fn check_arithmetic<F: Field>(rng: &mut ChaCha12Rng) {
+    for _ in 0..100 {
+        let a = rand_poly::<F, _>(rng.gen_range(0..20), rng);
+        let b = rand_poly::<F, _>(rng.gen_range(0..10), rng);
+        let x = F::rand(rng);
+
+        assert_eq!((&a + &b).eval(x), a.eval(x) + b.eval(x));
+        assert_eq!((&a - &b).eval(x), a.eval(x) - b.eval(x));
+        assert_eq!((&a * &b).eval(x), a.eval(x) * b.eval(x));
+        assert_eq!((&a - &a.clone()).degree(), None);
+
+        let (q, r) = a.div_rem(&b);
+        assert_eq!(&(&q * &b) + &r, a);
+        assert!(r.degree() < b.degree());
+
+        // Both are divisible by their gcd, which is divisible by any common factor.
+        let c = rand_poly::<F, _>(3, rng);
+        let g = (&a * &c).gcd(&(&b * &c));
+        assert!((&a * &c).div_rem(&g).1.is_zero());
+        assert!((&b * &c).div_rem(&g).1.is_zero());
+        assert!(g.div_rem(&c).1.is_zero());
+        assert_eq!(g.leading_coeff(), F::ONE);
+
+        // Product rule.
+        assert_eq!(
+            (&a * &b).derivative(),
+            &(&a.derivative() * &b) + &(&a * &b.derivative())
+        );
+    }
+}
+
+common::field_tests!(check_arithmetic);
+
+#[test]
+fn interpolate() {
+    let mut rng = ChaCha12Rng::seed_from_u64(1);
+    let p = rand_poly::<Mersenne61, _>(9, &mut rng);
+
+    let xs: Vec<_> = (0u32..10).map(Mersenne61::from).collect();
+    let ys = p.eval_many(&xs);
+    assert_eq!(Poly::interpolate(&xs, &ys), p);
+
+    // A polynomial through fewer points has lower degree, and agrees with the original at them.
+    let low = Poly::interpolate(&xs[..4], &ys[..4]);
+    assert!(low.degree() <= Some(3));
+    assert_eq!(low.eval_many(&xs[..4]), ys[..4]);
+    assert_eq!((&p - &low).eval_many(&xs[..4]), vec![Mersenne61::ZERO; 4]);
+}
+
+#[test]
+fn sharing_poly() {
+    let mut rng = ChaCha12Rng::seed_from_u64(2);
+    let (n, l, d) = (12, 3, 5);
+
+    let pos = PackedSharing::default_pos(n, l);
+    let pss = PackedSharing::<GF<16>>::new(d, n, &pos);
+    let secrets: Vec<_> = (0..l).map(|_| GF::rand(&mut rng)).collect();
+    let mut shares = pss.share(ArrayView::from(&secrets), &mut rng);
+
+    let poly = pss.interpolate(ArrayView::from(&shares));
+    assert!(poly.degree() <= Some(d as usize));
+    assert_eq!(poly.eval_many(&pos), secrets);
+
+    shares[0] += GF::ONE;
+    let poly = pss.interpolate(ArrayView::from(&shares));
+    assert_eq!(poly.degree(), Some(n as usize - 1));
+}
@@ -0,0 +1,72 @@
+//! Checks the arithmetic of `Mersenne61` and that packed sharing works over it.
+use ndarray::ArrayView;
+use pss::math::prime::Mersenne61;
+use pss::math::Field;
+use pss::sharing::PackedSharing;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+#[test]
+fn arithmetic() {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    let modulus = u128::from(Mersenne61::MODULUS);
+
+    for _ in 0..10_000 {
+        let x = rng.gen_range(0..Mersenne61::MODULUS);
+        let y = rng.gen_range(0..Mersenne61::MODULUS);
+        let (a, b) = (Mersenne61::from(x), Mersenne61::from(y));
+        let (x, y) = (u128::from(x), u128::from(y));
+
+        assert_eq!(u128::from(u64::from(a + b)), (x + y) % modulus);
+        assert_eq!(u128::from(u64::from(a - b)), (x + modulus - y) % modulus);
+        assert_eq!(u128::from(u64::from(a * b)), (x * y) % modulus);
+
+        if y != 0 {
+            assert_eq!((a / b) * b, a);
+        }
+    }
+
+    assert_eq!(Mersenne61::ZERO.inv(), None);
+    assert_eq!(Mersenne61::from(u64::MAX), Mersenne61::from(7u32));
+}
+
+#[test]
+fn dot_matches_naive() {
+    let mut rng = ChaCha12Rng::seed_from_u64(1);
+    let a: Vec<_> = (0..100).map(|_| Mersenne61::rand(&mut rng)).collect();
+    let b: Vec<_> = (0..100).map(|_| Mersenne61::rand(&mut rng)).collect();
+
+    let naive = a
+        .iter()
+        .zip(b.iter())
+        .fold(Mersenne61::ZERO, |acc, (&x, &y)| acc + x * y);
+    assert_eq!(<Mersenne61 as Field>::dot(&a, &b), naive);
+}
+
+#[test]
+fn share_and_recon() {
+    let mut rng = ChaCha12Rng::seed_from_u64(2);
+    let (n, l, d) = (10, 3, 6);
+
+    let pss = PackedSharing::<Mersenne61>::new(d, n, &PackedSharing::default_pos(n, l));
+    let secrets: Vec<_> = (0..l).map(|_| Mersenne61::rand(&mut rng)).collect();
+    let mut shares = pss.share(ArrayView::from(&secrets), &mut rng);
+
+    assert_eq!(pss.semihon_recon(ArrayView::from(&shares)), secrets);
+    assert_eq!(pss.recon(ArrayView::from(&shares)).unwrap(), secrets);
+
+    shares[0] += Mersenne61::ONE;
+    assert!(pss.recon(ArrayView::from(&shares)).is_err());
+}
+
+#[test]
+fn serialization() {
+    let x = Mersenne61::from(Mersenne61::MODULUS - 1);
+    let bytes = bincode::serialize(&x).unwrap();
+    assert_eq!(bytes.len(), Mersenne61::NUM_BYTES);
+    assert_eq!(bincode::deserialize::<Mersenne61>(&bytes).unwrap(), x);
+
+    // Only reduced values are valid encodings.
+    let bytes = bincode::serialize(&Mersenne61::MODULUS).unwrap();
+    assert!(bincode::deserialize::<Mersenne61>(&bytes).is_err());
+}
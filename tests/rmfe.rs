@@ -0,0 +1,56 @@
+//! Checks that RMFEs over `GF<W>` multiply bits component-wise, directly and through packed
+//! sharings.
+use pss::math::galois::{mul_slice, GF};
+use pss::math::rmfe::Rmfe;
+use pss::sharing::PackedSharing;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+fn check_width<const W: u8>(num_bits: usize) {
+    let rmfe = Rmfe::<W>::new();
+    assert_eq!(rmfe.num_bits(), num_bits);
+
+    let mut rng = ChaCha12Rng::seed_from_u64(W.into());
+    for _ in 0..1000 {
+        let x: Vec<bool> = (0..num_bits).map(|_| rng.gen()).collect();
+        let y: Vec<bool> = (0..num_bits).map(|_| rng.gen()).collect();
+        let xy: Vec<bool> = x.iter().zip(y.iter()).map(|(&a, &b)| a & b).collect();
+
+        assert_eq!(rmfe.decode(rmfe.encode(&x)), x);
+        assert_eq!(rmfe.decode(rmfe.encode(&x) * rmfe.encode(&y)), xy);
+    }
+}
+
+#[test]
+fn widths() {
+    check_width::<1>(1);
+    check_width::<4>(2);
+    check_width::<8>(2);
+    check_width::<12>(3);
+    check_width::<16>(4);
+    check_width::<20>(4);
+}
+
+#[test]
+fn packed_shares() {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    let (n, l, d) = (10, 2, 3);
+
+    let rmfe = Rmfe::<16>::new();
+    let pos = PackedSharing::default_pos(n, l);
+    let pss = PackedSharing::<GF<16>>::new(d, n, &pos);
+    let pss2 = PackedSharing::<GF<16>>::new(2 * d, n, &pos);
+
+    let len = rmfe.num_bits() * l as usize;
+    let x: Vec<bool> = (0..len).map(|_| rng.gen()).collect();
+    let y: Vec<bool> = (0..len).map(|_| rng.gen()).collect();
+    let xy: Vec<bool> = x.iter().zip(y.iter()).map(|(&a, &b)| a & b).collect();
+
+    let x_shares = rmfe.share(&pss, &x, &mut rng);
+    let y_shares = rmfe.share(&pss, &y, &mut rng);
+    assert_eq!(rmfe.recon(&pss, &x_shares).unwrap(), x);
+
+    let mut xy_shares = vec![GF::ZERO; n as usize];
+    mul_slice(&mut xy_shares, &x_shares, &y_shares);
+    assert_eq!(rmfe.recon(&pss2, &xy_shares).unwrap(), xy);
+}
@@ -0,0 +1,84 @@
+//! Checks the systematic Reed-Solomon encoder, parity-check matrix and syndromes.
+mod common;
+
+use ndarray::{Array1, Array2, ArrayView};
+use pss::math::galois::GF;
+use pss::math::linalg::{kernel, rank};
+use pss::math::poly::Poly;
+use pss::math::prime::Mersenne61;
+use pss::math::rs::RsCode;
+use pss::math::Field;
+use pss::sharing::PackedSharing;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+fn check_code<F: Field>(rng: &mut ChaCha12Rng) {
+    let (n, k) = (16, 6);
+    let points: Vec<F> = (0..n as u32).map(|i| F::from(i + 3)).collect();
+    let code = RsCode::new(&points, k);
+
+    let gen = code.gen_mat();
+    let check = code.parity_check_mat();
+    assert_eq!(gen.dim(), (n, k));
+    assert_eq!(check.dim(), (n - k, n));
+    assert_eq!(gen.slice(ndarray::s![..k, ..]), Array2::eye(k));
+    assert!(check.dot(&gen).iter().all(|v| v.is_zero()));
+    assert_eq!(rank(check.view()), n - k);
+    assert_eq!(kernel(check.view()).nrows(), k);
+
+    // Codewords are evaluations of polynomials of degree less than k.
+    let msg = Array1::from_shape_simple_fn(k, || F::rand(rng));
+    let word = code.encode(msg.view());
+    assert_eq!(word, gen.dot(&msg));
+    let poly = Poly::interpolate(&points[..k], &msg.to_vec());
+    assert_eq!(word.to_vec(), poly.eval_many(&points));
+    assert!(code.is_codeword(word.view()));
+    assert_eq!(code.syndrome(word.view()), check.dot(&word));
+
+    let mut bad = word.clone();
+    bad[k + 2] += F::ONE;
+    assert!(!code.is_codeword(bad.view()));
+    assert_eq!(code.syndrome(bad.view()), check.dot(&bad));
+
+    // Syndromes only depend on the error.
+    let err = &bad - &word;
+    assert_eq!(code.syndrome(bad.view()), code.syndrome(err.view()));
+
+    // Batched syndromes agree with individual ones.
+    let mut words = Array2::from_elem((n, 5), F::ZERO);
+    for mut col in words.columns_mut() {
+        let msg = Array1::from_shape_simple_fn(k, || F::rand(rng));
+        col.assign(&code.encode(msg.view()));
+    }
+    words[[0, 3]] += F::ONE;
+    let syn = code.syndromes(words.view());
+    for (i, col) in syn.columns().into_iter().enumerate() {
+        assert_eq!(col, code.syndrome(words.column(i)));
+        assert_eq!(col.iter().all(|v| v.is_zero()), i != 3);
+    }
+}
+
+common::field_tests!(check_code);
+
+#[test]
+fn sharing_consistency() {
+    let mut rng = ChaCha12Rng::seed_from_u64(1);
+    let (n, d, l) = (12, 6, 3);
+    let pss = PackedSharing::<GF<16>>::new(d, n, &PackedSharing::default_pos(n, l));
+    let code = pss.rs_code();
+
+    let secrets: Vec<_> = (0..l).map(|_| GF::rand(&mut rng)).collect();
+    let mut shares = pss.share(ArrayView::from(&secrets), &mut rng);
+    assert!(code.is_codeword(ArrayView::from(&shares)));
+    assert!(code.is_codeword(ArrayView::from(&pss.rand(&mut rng))));
+
+    shares[0] += GF::ONE;
+    assert!(!code.is_codeword(ArrayView::from(&shares)));
+}
+
+#[test]
+#[should_panic(expected = "Dimension mismatch.")]
+fn syndromes_shape_mismatch() {
+    let code = RsCode::<Mersenne61>::new(&PackedSharing::share_pos(10), 4);
+    code.syndromes(Array2::from_elem((9, 3), Mersenne61::ZERO).view());
+}
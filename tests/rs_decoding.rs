@@ -0,0 +1,117 @@
+//! Checks error correction of packed sharings using Reed-Solomon decoding.
+mod common;
+
+use ndarray::ArrayView;
+use pss::math::poly::Poly;
+use pss::math::prime::Mersenne61;
+use pss::math::ring::GR;
+use pss::math::{rs, Field};
+use pss::sharing::PackedSharing;
+use pss::ProtoErrorKind;
+use rand::seq::index::sample;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+fn check_correction<F: Field>(rng: &mut ChaCha12Rng) {
+    let (n, d, l) = (20, 9, 4);
+    let pss = PackedSharing::<F>::new(d, n, &PackedSharing::default_pos(n, l));
+    let max_errors = rs::max_errors(n as usize, (d + 1) as usize);
+    assert_eq!(max_errors, 5);
+
+    for num_errors in 0..=max_errors {
+        let secrets: Vec<_> = (0..l).map(|_| F::rand(rng)).collect();
+        let mut shares = pss.share(ArrayView::from(&secrets), rng);
+
+        let mut cheaters = sample(rng, n as usize, num_errors).into_vec();
+        cheaters.sort_unstable();
+        for &i in &cheaters {
+            shares[i] += F::ONE;
+        }
+
+        let (res, found) = pss.robust_recon(ArrayView::from(&shares)).unwrap();
+        assert_eq!(res, secrets);
+        assert_eq!(found, cheaters);
+    }
+
+    // Beyond the decoding radius errors are still detected.
+    let secrets: Vec<_> = (0..l).map(|_| F::rand(rng)).collect();
+    let mut shares = pss.share(ArrayView::from(&secrets), rng);
+    for i in 0..8 {
+        shares[2 * i] += F::rand(rng);
+    }
+    assert!(pss.robust_recon(ArrayView::from(&shares)).is_err());
+}
+
+common::field_tests!(check_correction);
+
+#[test]
+fn detection_without_redundancy() {
+    let mut rng = ChaCha12Rng::seed_from_u64(1);
+    let (n, d, l) = (10, 8, 3);
+    let pss = PackedSharing::<Mersenne61>::new(d, n, &PackedSharing::default_pos(n, l));
+
+    let secrets: Vec<_> = (0..l).map(|_| Mersenne61::rand(&mut rng)).collect();
+    let mut shares = pss.share(ArrayView::from(&secrets), &mut rng);
+    let (res, cheaters) = pss.robust_recon(ArrayView::from(&shares)).unwrap();
+    assert_eq!(res, secrets);
+    assert!(cheaters.is_empty());
+
+    shares[3] += Mersenne61::ONE;
+    assert!(pss.robust_recon(ArrayView::from(&shares)).is_err());
+}
+
+#[test]
+fn decode_polynomial() {
+    let mut rng = ChaCha12Rng::seed_from_u64(2);
+    let xs: Vec<_> = (0..15u32).map(Mersenne61::from).collect();
+    let poly = Poly::new((0..5).map(|_| Mersenne61::rand(&mut rng)).collect());
+
+    let mut ys = poly.eval_many(&xs);
+    ys[1] += Mersenne61::ONE;
+    ys[7] += Mersenne61::ONE;
+    ys[14] += Mersenne61::ONE;
+
+    let (res, errors) = rs::decode(&xs, &ys, 5).unwrap();
+    assert_eq!(res, poly);
+    assert_eq!(errors, vec![1, 7, 14]);
+}
+
+#[test]
+fn galois_ring() {
+    // With D = 3 only 8 positions are available, and an eighth of the elements are non-units, so
+    // the euclidean algorithm regularly hits non-invertible leading coefficients.
+    type R = GR<8, 3>;
+    let mut rng = ChaCha12Rng::seed_from_u64(3);
+    let (n, d, l) = (6, 1, 1);
+    let pss = PackedSharing::<R>::new(d, n, &PackedSharing::default_pos(n, l));
+    assert_eq!(rs::max_errors(n as usize, (d + 1) as usize), 2);
+
+    let mut num_failures = 0;
+    for trial in 0..500 {
+        let secrets = vec![R::rand(&mut rng)];
+        let mut shares = pss.share(ArrayView::from(&secrets), &mut rng);
+
+        let mut cheaters = sample(&mut rng, n as usize, 1 + trial % 2).into_vec();
+        cheaters.sort_unstable();
+        for &i in &cheaters {
+            let mut err = R::rand(&mut rng);
+            while err == R::ZERO {
+                err = R::rand(&mut rng);
+            }
+            shares[i] += err;
+        }
+
+        match pss.robust_recon(ArrayView::from(&shares)) {
+            Ok((res, found)) => {
+                assert_eq!(res, secrets);
+                assert_eq!(found, cheaters);
+            }
+            Err(e) => {
+                assert_eq!(e, ProtoErrorKind::MaliciousBehavior);
+                num_failures += 1;
+            }
+        }
+    }
+    assert!(num_failures > 0);
+    assert!(num_failures < 250, "{num_failures}");
+}
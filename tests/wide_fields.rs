@@ -0,0 +1,80 @@
+//! Checks the arithmetic of `GF64` and `GF128` against schoolbook multiplication, and that lifting
+//! from `GF<W>` is a field homomorphism.
+use pss::math::galois::{GF, GF128, GF64};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+/// Shift-and-add multiplication modulo x^k + `tail`.
+fn reference_mul(mut x: u128, y: u128, k: u32, tail: u128) -> u128 {
+    let mask = u128::MAX >> (128 - k);
+    let mut res = 0;
+    for i in 0..k {
+        if (y >> i) & 1 == 1 {
+            res ^= x;
+        }
+
+        let carry = (x >> (k - 1)) & 1;
+        x = (x << 1) & mask;
+        if carry == 1 {
+            x ^= tail;
+        }
+    }
+    res
+}
+
+#[test]
+fn gf64_mul() {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    for _ in 0..10_000 {
+        let (x, y): (u64, u64) = (rng.gen(), rng.gen());
+        let prod: u64 = (GF64::from(x) * GF64::from(y)).into();
+        assert_eq!(prod as u128, reference_mul(x.into(), y.into(), 64, 0b11011));
+    }
+}
+
+#[test]
+fn gf128_mul() {
+    let mut rng = ChaCha12Rng::seed_from_u64(1);
+    for _ in 0..10_000 {
+        let (x, y): (u128, u128) = (rng.gen(), rng.gen());
+        let prod: u128 = (GF128::from(x) * GF128::from(y)).into();
+        assert_eq!(prod, reference_mul(x, y, 128, 0b10000111));
+    }
+}
+
+#[test]
+fn inverse() {
+    let mut rng = ChaCha12Rng::seed_from_u64(2);
+    for _ in 0..100 {
+        let x = GF64::rand(&mut rng);
+        assert_eq!(x * x.inv().unwrap(), GF64::ONE);
+
+        let y = GF128::rand(&mut rng);
+        assert_eq!(y / y, GF128::ONE);
+    }
+    assert_eq!(GF64::ZERO.inv(), None);
+    assert_eq!(GF128::ZERO.inv(), None);
+}
+
+fn check_lift<const W: u8>() {
+    let mut rng = ChaCha12Rng::seed_from_u64(W.into());
+    for _ in 0..1000 {
+        let (x, y) = (GF::<W>::rand(&mut rng), GF::<W>::rand(&mut rng));
+
+        assert_eq!(GF64::lift(x + y), GF64::lift(x) + GF64::lift(y));
+        assert_eq!(GF64::lift(x * y), GF64::lift(x) * GF64::lift(y));
+        assert_eq!(GF128::lift(x + y), GF128::lift(x) + GF128::lift(y));
+        assert_eq!(GF128::lift(x * y), GF128::lift(x) * GF128::lift(y));
+    }
+    assert_eq!(GF64::lift(GF::<W>::ONE), GF64::ONE);
+    assert_eq!(GF128::lift(GF::<W>::ONE), GF128::ONE);
+}
+
+#[test]
+fn lift() {
+    check_lift::<1>();
+    check_lift::<2>();
+    check_lift::<4>();
+    check_lift::<8>();
+    check_lift::<16>();
+}